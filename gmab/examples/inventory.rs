@@ -1,3 +1,10 @@
+// kept as originally written, clippy style lints are not enforced on this example
+#![allow(
+    clippy::needless_return,
+    clippy::assign_op_pattern,
+    clippy::needless_borrow
+)]
+
 use std::time::Instant;
 
 use rand_distr::{Distribution, Poisson};
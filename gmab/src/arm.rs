@@ -1,7 +1,163 @@
-use std::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+use crate::config::Objective;
+use crate::error::GaError;
+use crate::gmab::derive_seed;
+use crate::{Map, Set};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
 
 pub trait OptimizationFn {
     fn evaluate(&self, action_vector: &[i32]) -> f64;
+
+    fn evaluate_batch(&self, action_vectors: &[Vec<i32>]) -> Vec<f64> {
+        action_vectors
+            .iter()
+            .map(|action_vector| self.evaluate(action_vector))
+            .collect()
+    }
+
+    // the `replication`-th evaluation of the action vector, i.e. its number of pulls so far
+    fn evaluate_replication(&self, action_vector: &[i32], replication: u64) -> f64 {
+        let _ = replication;
+        self.evaluate(action_vector)
+    }
+
+    // called with the number of generations elapsed after every generation, only
+    // time-varying objectives make use of it
+    fn set_time(&self, time: usize) {
+        let _ = time;
+    }
+}
+
+// simulators driven by an explicit random seed
+pub trait CrnOptimizationFn {
+    fn eval(&self, action_vector: &[i32], seed: u64) -> f64;
+}
+
+// common random numbers: the n-th evaluation of every arm runs with the same seed, so
+// that arms are compared under matched randomness; batch evaluation always uses the seed
+// of the first replication
+pub struct Crn<C: CrnOptimizationFn> {
+    function: C,
+    seed: u64,
+}
+
+impl<C: CrnOptimizationFn> Crn<C> {
+    pub fn new(function: C, seed: u64) -> Self {
+        Crn { function, seed }
+    }
+}
+
+impl<C: CrnOptimizationFn> OptimizationFn for Crn<C> {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        self.evaluate_replication(action_vector, 0)
+    }
+
+    fn evaluate_replication(&self, action_vector: &[i32], replication: u64) -> f64 {
+        self.function
+            .eval(action_vector, derive_seed(self.seed, replication))
+    }
+}
+
+// objectives whose optimum moves over time, e.g. in tracking problems
+pub trait DynamicOptimizationFn {
+    fn evaluate_at(&self, action_vector: &[i32], time: usize) -> f64;
+}
+
+// makes a dynamic objective usable as optimization function, evaluated at the generation
+// the GA is in; see `GmabConfig::with_dynamic_tracking` to follow a moving optimum
+pub struct Dynamic<D: DynamicOptimizationFn> {
+    function: D,
+    time: AtomicUsize,
+}
+
+impl<D: DynamicOptimizationFn> Dynamic<D> {
+    pub fn new(function: D) -> Self {
+        Dynamic {
+            function,
+            time: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn time(&self) -> usize {
+        self.time.load(Ordering::Relaxed)
+    }
+}
+
+impl<D: DynamicOptimizationFn> OptimizationFn for Dynamic<D> {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        self.function.evaluate_at(action_vector, self.time())
+    }
+
+    fn set_time(&self, time: usize) {
+        self.time.store(time, Ordering::Relaxed);
+    }
+}
+
+// simulators that are cheaper to run on many inputs at once
+pub trait BatchOptimizationFn {
+    fn eval_batch(&self, action_vectors: &[Vec<i32>]) -> Vec<f64>;
+}
+
+// makes a batch simulator usable as optimization function, together with batch evaluation
+// in the config every generation is simulated in one call
+pub struct Batched<B: BatchOptimizationFn>(pub B);
+
+impl<B: BatchOptimizationFn> OptimizationFn for Batched<B> {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        self.0.eval_batch(&[action_vector.to_vec()])[0]
+    }
+
+    fn evaluate_batch(&self, action_vectors: &[Vec<i32>]) -> Vec<f64> {
+        self.0.eval_batch(action_vectors)
+    }
+}
+
+// memoizes a deterministic optimization function independently of the fitness cache of the
+// GA, clones share their cache so that it can be reused by several instances
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct Cached<F: OptimizationFn> {
+    function: F,
+    cache: Arc<Mutex<Map<Vec<i32>, f64>>>,
+}
+
+#[cfg(feature = "std")]
+impl<F: OptimizationFn> Cached<F> {
+    pub fn new(function: F) -> Self {
+        Cached {
+            function,
+            cache: Arc::new(Mutex::new(Map::new())),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: OptimizationFn> OptimizationFn for Cached<F> {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        if let Some(&fitness) = self.cache.lock().unwrap().get(action_vector) {
+            return fitness;
+        }
+        // the lock is not held while the function runs
+        let fitness = self.function.evaluate(action_vector);
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(action_vector.to_vec(), fitness);
+        fitness
+    }
+
+    // cached results of a dynamic objective are not invalidated by the time
+    fn set_time(&self, time: usize) {
+        self.function.set_time(time);
+    }
 }
 
 impl<F: Fn(&[i32]) -> f64> OptimizationFn for F {
@@ -11,47 +167,224 @@ impl<F: Fn(&[i32]) -> f64> OptimizationFn for F {
 }
 
 #[derive(Debug)]
-pub(crate) struct Arm {
+pub struct Arm {
     action_vector: Vec<i32>,
-    reward: f64,
+    // running mean and sum of squared deviations (Welford's algorithm)
+    mean_reward: f64,
+    m2: f64,
     num_pulls: i32,
+    active_mask: Option<Vec<bool>>,
 }
 
 impl Arm {
-    pub(crate) fn new(action_vector: &[i32]) -> Self {
+    pub fn new(action_vector: &[i32]) -> Self {
         Self {
-            reward: 0.0,
+            mean_reward: 0.0,
+            m2: 0.0,
             num_pulls: 0,
             action_vector: action_vector.to_vec(),
+            active_mask: None,
+        }
+    }
+
+    // genes marked inactive are neither mutated nor exchanged during crossover
+    pub fn with_active_mask(mut self, active_mask: Vec<bool>) -> Self {
+        assert_eq!(
+            active_mask.len(),
+            self.action_vector.len(),
+            "active mask must cover every gene"
+        );
+        self.active_mask = Some(active_mask);
+        self
+    }
+
+    pub fn get_active_mask(&self) -> Option<&[bool]> {
+        self.active_mask.as_deref()
+    }
+
+    pub fn is_gene_active(&self, index: usize) -> bool {
+        match &self.active_mask {
+            Some(mask) => mask[index],
+            None => true,
         }
     }
 
     pub(crate) fn pull<F: OptimizationFn>(&mut self, opt_fn: &F) -> f64 {
         let g = opt_fn.evaluate(&self.action_vector);
 
-        self.reward += g;
-        self.num_pulls += 1;
+        self.record_reward(g);
 
         g
     }
 
-    pub(crate) fn get_num_pulls(&self) -> i32 {
+    pub(crate) fn record_reward(&mut self, reward: f64) {
+        self.num_pulls += 1;
+        let delta = reward - self.mean_reward;
+        self.mean_reward += delta / self.num_pulls as f64;
+        self.m2 += delta * (reward - self.mean_reward);
+    }
+
+    // combines the reward statistics of two arms with the same action vector
+    // (parallel variant of Welford's algorithm)
+    pub fn merge_statistics(&mut self, other: &Arm) {
+        if other.num_pulls == 0 {
+            return;
+        }
+        let num_pulls = self.num_pulls + other.num_pulls;
+        let delta = other.mean_reward - self.mean_reward;
+
+        self.mean_reward += delta * other.num_pulls as f64 / num_pulls as f64;
+        self.m2 += other.m2
+            + delta * delta * self.num_pulls as f64 * other.num_pulls as f64 / num_pulls as f64;
+        self.num_pulls = num_pulls;
+    }
+
+    // forgets all rewards, e.g. after the objective changed
+    pub(crate) fn reset_statistics(&mut self) {
+        self.mean_reward = 0.0;
+        self.m2 = 0.0;
+        self.num_pulls = 0;
+    }
+
+    pub fn get_num_pulls(&self) -> i32 {
         self.num_pulls
     }
 
+    // only used for progress printing, which needs std
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
     pub(crate) fn get_function_value<F: OptimizationFn>(&self, opt_fn: &F) -> f64 {
         opt_fn.evaluate(&self.action_vector)
     }
 
-    pub(crate) fn get_action_vector(&self) -> &[i32] {
+    pub fn get_action_vector(&self) -> &[i32] {
         &self.action_vector
     }
 
-    pub(crate) fn get_mean_reward(&self) -> f64 {
-        if self.num_pulls == 0 {
+    pub fn get_mean_reward(&self) -> f64 {
+        self.mean_reward
+    }
+
+    // population variance of the observed rewards, zero before the second pull
+    pub fn get_reward_variance(&self) -> f64 {
+        if self.num_pulls < 2 {
             return 0.0;
         }
-        self.reward / self.num_pulls as f64
+        self.m2 / self.num_pulls as f64
+    }
+
+    // number of genes that differ
+    pub fn hamming_distance(&self, other: &Arm) -> usize {
+        self.assert_same_dimension(other);
+        self.action_vector
+            .iter()
+            .zip(&other.action_vector)
+            .filter(|(a, b)| a != b)
+            .count()
+    }
+
+    pub fn euclidean_distance(&self, other: &Arm) -> f64 {
+        self.assert_same_dimension(other);
+        self.action_vector
+            .iter()
+            .zip(&other.action_vector)
+            .map(|(&a, &b)| (a as f64 - b as f64).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    fn assert_same_dimension(&self, other: &Arm) {
+        assert_eq!(
+            self.action_vector.len(),
+            other.action_vector.len(),
+            "arms must have the same dimension"
+        );
+    }
+
+    // normal approximation of the `confidence` interval of the mean reward, unbounded
+    // before the second pull
+    pub fn get_confidence_interval(&self, confidence: f64) -> (f64, f64) {
+        assert!(
+            confidence > 0.0 && confidence < 1.0,
+            "confidence must lie in (0, 1)"
+        );
+        if self.num_pulls < 2 {
+            return (f64::NEG_INFINITY, f64::INFINITY);
+        }
+
+        let n = self.num_pulls as f64;
+        let standard_error = (self.m2 / (n - 1.0) / n).sqrt();
+        let half_width = normal_quantile(0.5 + confidence / 2.0) * standard_error;
+        (self.mean_reward - half_width, self.mean_reward + half_width)
+    }
+}
+
+// standard normal distribution function, erf by Abramowitz and Stegun 7.1.26 with an
+// absolute error below 1.5e-7
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    const P: f64 = 0.3275911;
+    const A: [f64; 5] = [
+        0.254829592,
+        -0.284496736,
+        1.421413741,
+        -1.453152027,
+        1.061405429,
+    ];
+
+    let z = x.abs() / core::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + P * z);
+    let polynomial = ((((A[4] * t + A[3]) * t + A[2]) * t + A[1]) * t + A[0]) * t;
+    let erf = 1.0 - polynomial * (-z * z).exp();
+    0.5 * (1.0 + erf.copysign(x))
+}
+
+// inverse of the standard normal distribution function, Acklam's rational approximation
+// with a relative error below 1.2e-9
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
     }
 }
 
@@ -59,12 +392,25 @@ impl Clone for Arm {
     fn clone(&self) -> Self {
         Self {
             action_vector: self.action_vector.clone(),
-            reward: self.reward,
+            mean_reward: self.mean_reward,
+            m2: self.m2,
             num_pulls: self.num_pulls,
+            active_mask: self.active_mask.clone(),
         }
     }
 }
 
+impl TryFrom<Vec<i32>> for Arm {
+    type Error = GaError;
+
+    fn try_from(action_vector: Vec<i32>) -> Result<Self, Self::Error> {
+        if action_vector.is_empty() {
+            return Err(GaError::EmptyActionVector);
+        }
+        Ok(Arm::new(&action_vector))
+    }
+}
+
 impl PartialEq for Arm {
     fn eq(&self, other: &Self) -> bool {
         self.action_vector == other.action_vector
@@ -79,9 +425,130 @@ impl Hash for Arm {
     }
 }
 
+// union of two populations, arms present in both are merged into one with combined statistics
+pub fn merge_populations(a: Vec<Arm>, b: Vec<Arm>) -> Vec<Arm> {
+    let mut merged: Vec<Arm> = Vec::with_capacity(a.len() + b.len());
+    let mut positions: Map<Vec<i32>, usize> = Map::new();
+
+    for arm in a.into_iter().chain(b) {
+        match positions.get(arm.get_action_vector()) {
+            Some(&position) => merged[position].merge_statistics(&arm),
+            None => {
+                positions.insert(arm.get_action_vector().to_vec(), merged.len());
+                merged.push(arm);
+            }
+        }
+    }
+
+    merged
+}
+
+// changes between two snapshots of a population by action vector, arms in their original
+// order; surviving arms are taken from `after` with its statistics
+#[derive(Debug, Clone, PartialEq)]
+pub struct PopulationDiff {
+    pub added: Vec<Arm>,
+    pub removed: Vec<Arm>,
+    pub surviving: Vec<Arm>,
+}
+
+pub fn population_diff(before: &[Arm], after: &[Arm]) -> PopulationDiff {
+    let before_vectors: Set<&[i32]> = before.iter().map(Arm::get_action_vector).collect();
+    let after_vectors: Set<&[i32]> = after.iter().map(Arm::get_action_vector).collect();
+
+    let mut diff = PopulationDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        surviving: Vec::new(),
+    };
+    let mut seen: Set<&[i32]> = Set::new();
+    for arm in after {
+        if !seen.insert(arm.get_action_vector()) {
+            continue;
+        }
+        if before_vectors.contains(arm.get_action_vector()) {
+            diff.surviving.push(arm.clone());
+        } else {
+            diff.added.push(arm.clone());
+        }
+    }
+    seen.clear();
+    for arm in before {
+        if seen.insert(arm.get_action_vector()) && !after_vectors.contains(arm.get_action_vector())
+        {
+            diff.removed.push(arm.clone());
+        }
+    }
+
+    diff
+}
+
+// arms in their original order with duplicate action vectors reduced to the first one of
+// the best fitness
+pub fn prune_arms(arms: Vec<(Arm, f64)>, objective: Objective) -> Vec<(Arm, f64)> {
+    let mut pruned: Vec<(Arm, f64)> = Vec::with_capacity(arms.len());
+    let mut positions: Map<Vec<i32>, usize> = Map::new();
+
+    for (arm, fitness) in arms {
+        match positions.get(arm.get_action_vector()) {
+            Some(&position) => {
+                if objective.cost(fitness) < objective.cost(pruned[position].1) {
+                    pruned[position].1 = fitness;
+                }
+            }
+            None => {
+                positions.insert(arm.get_action_vector().to_vec(), pruned.len());
+                pruned.push((arm, fitness));
+            }
+        }
+    }
+
+    pruned
+}
+
+// multi-objective counterpart of prune_arms, additionally removing every arm dominated by
+// another one (no worse in all objectives and better in at least one)
+pub fn prune_dominated_arms(
+    arms: Vec<(Arm, Vec<f64>)>,
+    objective: Objective,
+) -> Vec<(Arm, Vec<f64>)> {
+    let dominates = |a: &[f64], b: &[f64]| {
+        let mut costs = a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| (objective.cost(*a), objective.cost(*b)));
+        costs.clone().all(|(a, b)| a <= b) && costs.any(|(a, b)| a < b)
+    };
+
+    let mut unique: Vec<(Arm, Vec<f64>)> = Vec::with_capacity(arms.len());
+    let mut seen: Set<Vec<i32>> = Set::new();
+    for (arm, fitness) in arms {
+        if seen.insert(arm.get_action_vector().to_vec()) {
+            unique.push((arm, fitness));
+        }
+    }
+
+    let dominated: Vec<bool> = unique
+        .iter()
+        .map(|(_, fitness)| {
+            unique
+                .iter()
+                .any(|(_, other_fitness)| dominates(other_fitness, fitness))
+        })
+        .collect();
+    unique
+        .into_iter()
+        .zip(dominated)
+        .filter(|(_, dominated)| !dominated)
+        .map(|(arm, _)| arm)
+        .collect()
+}
+
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     // Mock optimization function for testing
     fn mock_opti_function(_vec: &[i32]) -> f64 {
@@ -148,4 +615,246 @@ mod tests {
         assert_eq!(arm.get_num_pulls(), cloned_arm.get_num_pulls());
         assert_eq!(arm.get_mean_reward(), cloned_arm.get_mean_reward());
     }
+
+    #[test]
+    fn test_arm_active_mask() {
+        let arm = Arm::new(&[1, 2]);
+        assert!(arm.is_gene_active(0));
+        assert!(arm.is_gene_active(1));
+
+        let masked_arm = Arm::new(&[1, 2]).with_active_mask(vec![true, false]);
+        assert!(masked_arm.is_gene_active(0));
+        assert!(!masked_arm.is_gene_active(1));
+        assert_eq!(
+            masked_arm.clone().get_active_mask(),
+            Some(&[true, false][..])
+        );
+
+        // the mask does not change the identity of an arm
+        assert_eq!(arm, masked_arm);
+    }
+
+    #[test]
+    fn test_reward_variance_is_numerically_stable() {
+        // a large offset makes the naive sum-of-squares formula cancel catastrophically
+        let offset = 1e9;
+        let rewards: Vec<f64> = (0..1000).map(|i| offset + (i % 4) as f64).collect();
+
+        let mut arm = Arm::new(&[1, 2]);
+        for reward in &rewards {
+            arm.record_reward(*reward);
+        }
+
+        let n = rewards.len() as f64;
+        let naive_mean = rewards.iter().sum::<f64>() / n;
+        let naive_variance =
+            rewards.iter().map(|reward| reward * reward).sum::<f64>() / n - naive_mean * naive_mean;
+
+        // rewards cycle through offset + {0, 1, 2, 3}, whose variance is 1.25
+        assert!((arm.get_mean_reward() - (offset + 1.5)).abs() < 1e-6);
+        assert!((arm.get_reward_variance() - 1.25).abs() < 1e-6);
+        assert!((naive_variance - 1.25).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_reward_variance_with_few_pulls() {
+        let mut arm = Arm::new(&[1, 2]);
+        assert_eq!(arm.get_reward_variance(), 0.0);
+
+        arm.record_reward(2.0);
+        assert_eq!(arm.get_reward_variance(), 0.0);
+
+        arm.record_reward(4.0);
+        assert_eq!(arm.get_mean_reward(), 3.0);
+        assert_eq!(arm.get_reward_variance(), 1.0);
+    }
+
+    #[test]
+    fn test_population_diff() {
+        let before = vec![Arm::new(&[0, 0]), Arm::new(&[1, 2]), Arm::new(&[3, 4])];
+        let mut survivor = Arm::new(&[1, 2]);
+        survivor.record_reward(2.0);
+        let after = vec![
+            Arm::new(&[5, 6]),
+            survivor,
+            Arm::new(&[5, 6]),
+            Arm::new(&[7, 8]),
+        ];
+
+        let diff = population_diff(&before, &after);
+        assert_eq!(diff.added, vec![Arm::new(&[5, 6]), Arm::new(&[7, 8])]);
+        assert_eq!(diff.removed, vec![Arm::new(&[0, 0]), Arm::new(&[3, 4])]);
+        assert_eq!(diff.surviving, vec![Arm::new(&[1, 2])]);
+        // survivors carry their statistics after the change
+        assert_eq!(diff.surviving[0].get_num_pulls(), 1);
+
+        let unchanged = population_diff(&before, &before);
+        assert!(unchanged.added.is_empty() && unchanged.removed.is_empty());
+        assert_eq!(unchanged.surviving, before);
+    }
+
+    #[test]
+    fn test_merge_populations() {
+        let mut shared_a = Arm::new(&[1, 2]);
+        shared_a.record_reward(1.0);
+        shared_a.record_reward(3.0);
+        let mut shared_b = Arm::new(&[1, 2]);
+        shared_b.record_reward(5.0);
+        let mut only_a = Arm::new(&[0, 0]);
+        only_a.record_reward(7.0);
+        let only_b = Arm::new(&[3, 4]);
+
+        let merged = merge_populations(vec![only_a, shared_a], vec![shared_b, only_b]);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].get_action_vector(), &[0, 0]);
+        assert_eq!(merged[1].get_action_vector(), &[1, 2]);
+        assert_eq!(merged[2].get_action_vector(), &[3, 4]);
+
+        // statistics match pulling a single arm with rewards 1, 3 and 5
+        assert_eq!(merged[1].get_num_pulls(), 3);
+        assert_eq!(merged[1].get_mean_reward(), 3.0);
+        assert!((merged[1].get_reward_variance() - 8.0 / 3.0).abs() < 1e-12);
+        assert_eq!(merged[0].get_mean_reward(), 7.0);
+        assert_eq!(merged[2].get_num_pulls(), 0);
+    }
+
+    #[test]
+    fn test_prune_arms() {
+        let arms = vec![
+            (Arm::new(&[1, 2]), 4.0),
+            (Arm::new(&[0, 0]), 7.0),
+            (Arm::new(&[1, 2]), 3.0),
+            (Arm::new(&[0, 0]), 9.0),
+        ];
+
+        let pruned = prune_arms(arms.clone(), Objective::Minimize);
+        assert_eq!(
+            pruned,
+            vec![(Arm::new(&[1, 2]), 3.0), (Arm::new(&[0, 0]), 7.0)]
+        );
+        let pruned = prune_arms(arms, Objective::Maximize);
+        assert_eq!(
+            pruned,
+            vec![(Arm::new(&[1, 2]), 4.0), (Arm::new(&[0, 0]), 9.0)]
+        );
+    }
+
+    #[test]
+    fn test_prune_dominated_arms() {
+        let arms = vec![
+            (Arm::new(&[0, 0]), vec![1.0, 5.0]),
+            (Arm::new(&[1, 1]), vec![3.0, 3.0]),
+            (Arm::new(&[0, 0]), vec![1.0, 5.0]),
+            // dominated by [1, 1]
+            (Arm::new(&[2, 2]), vec![4.0, 3.0]),
+            (Arm::new(&[3, 3]), vec![5.0, 1.0]),
+        ];
+
+        let pruned = prune_dominated_arms(arms.clone(), Objective::Minimize);
+        let action_vectors: Vec<&[i32]> = pruned
+            .iter()
+            .map(|(arm, _)| arm.get_action_vector())
+            .collect();
+        assert_eq!(action_vectors, vec![&[0, 0], &[1, 1], &[3, 3]]);
+
+        // when maximizing [2, 2] dominates [1, 1] instead
+        let pruned = prune_dominated_arms(arms, Objective::Maximize);
+        let action_vectors: Vec<&[i32]> = pruned
+            .iter()
+            .map(|(arm, _)| arm.get_action_vector())
+            .collect();
+        assert_eq!(action_vectors, vec![&[0, 0], &[2, 2], &[3, 3]]);
+    }
+
+    #[test]
+    fn test_arm_try_from() {
+        assert_eq!(Arm::try_from(vec![]), Err(GaError::EmptyActionVector));
+
+        let arm = Arm::try_from(vec![1, 2]).unwrap();
+        assert_eq!(arm.get_action_vector(), &[1, 2]);
+        assert_eq!(arm.get_num_pulls(), 0);
+    }
+
+    #[test]
+    fn test_arm_confidence_interval() {
+        let mut arm = Arm::new(&[1, 2]);
+        assert_eq!(
+            arm.get_confidence_interval(0.95),
+            (f64::NEG_INFINITY, f64::INFINITY)
+        );
+
+        for reward in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            arm.record_reward(reward);
+        }
+        // sample variance 2.5, standard error sqrt(2.5 / 5), z = 1.959964
+        let half_width = 1.959964 * 0.5f64.sqrt();
+        let (lower, upper) = arm.get_confidence_interval(0.95);
+        assert!((lower - (3.0 - half_width)).abs() < 1e-6);
+        assert!((upper - (3.0 + half_width)).abs() < 1e-6);
+
+        assert!((normal_quantile(0.005) + 2.575829).abs() < 1e-6);
+        assert!((normal_quantile(0.5)).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_cached_memoizes_evaluations() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&calls);
+        let cached = Cached::new(move |x: &[i32]| {
+            counted.fetch_add(1, Ordering::SeqCst);
+            (x[0] * x[1]) as f64
+        });
+
+        assert_eq!(cached.evaluate(&[2, 3]), 6.0);
+        assert_eq!(cached.evaluate(&[2, 3]), 6.0);
+        assert_eq!(cached.evaluate_replication(&[2, 3], 4), 6.0);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert_eq!(cached.evaluate(&[4, 3]), 12.0);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // clones share the cache
+        let clone = cached.clone();
+        assert_eq!(
+            clone.evaluate_batch(&[vec![2, 3], vec![4, 3]]),
+            vec![6.0, 12.0]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_cached_forwards_time() {
+        struct Clock;
+        impl DynamicOptimizationFn for Clock {
+            fn evaluate_at(&self, x: &[i32], time: usize) -> f64 {
+                (x[0] + time as i32) as f64
+            }
+        }
+
+        let cached = Cached::new(Dynamic::new(Clock));
+        cached.set_time(3);
+        assert_eq!(cached.evaluate(&[1]), 4.0);
+    }
+
+    #[test]
+    fn test_arm_distances() {
+        let a = Arm::new(&[0, 0, 5]);
+        let b = Arm::new(&[3, 4, 5]);
+
+        assert_eq!(a.hamming_distance(&b), 2);
+        assert_eq!(a.euclidean_distance(&b), 5.0);
+        assert_eq!(a.hamming_distance(&a.clone()), 0);
+        assert_eq!(b.euclidean_distance(&b.clone()), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "arms must have the same dimension")]
+    fn test_arm_distance_dimension_mismatch() {
+        Arm::new(&[1, 2]).hamming_distance(&Arm::new(&[1, 2, 3]));
+    }
 }
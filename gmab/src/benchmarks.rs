@@ -0,0 +1,140 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+use crate::arm::OptimizationFn;
+
+// test function with a known optimum, genes are mapped to x = gene * resolution
+pub trait Benchmark: OptimizationFn {
+    fn bounds(&self) -> Vec<(i32, i32)>;
+
+    fn optimum(&self) -> Vec<i32>;
+
+    fn optimal_fitness(&self) -> f64 {
+        0.0
+    }
+}
+
+// sum of x^2 on [-50, 50] per gene, minimal at the origin
+pub struct Sphere {
+    pub dimension: usize,
+}
+
+impl OptimizationFn for Sphere {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        action_vector.iter().map(|&x| (x as f64).powi(2)).sum()
+    }
+}
+
+impl Benchmark for Sphere {
+    fn bounds(&self) -> Vec<(i32, i32)> {
+        vec![(-50, 50); self.dimension]
+    }
+
+    fn optimum(&self) -> Vec<i32> {
+        vec![0; self.dimension]
+    }
+}
+
+// highly multimodal, x in [-5.12, 5.12] with a resolution of 0.01, minimal at the origin
+pub struct Rastrigin {
+    pub dimension: usize,
+}
+
+impl OptimizationFn for Rastrigin {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        action_vector
+            .iter()
+            .map(|&gene| {
+                let x = gene as f64 * 0.01;
+                x * x - 10.0 * (2.0 * PI * x).cos() + 10.0
+            })
+            .sum()
+    }
+}
+
+impl Benchmark for Rastrigin {
+    fn bounds(&self) -> Vec<(i32, i32)> {
+        vec![(-512, 512); self.dimension]
+    }
+
+    fn optimum(&self) -> Vec<i32> {
+        vec![0; self.dimension]
+    }
+}
+
+// narrow curved valley, x in [-2.048, 2.048] with a resolution of 0.001, minimal at
+// x = (1, ..., 1)
+pub struct Rosenbrock {
+    pub dimension: usize,
+}
+
+impl OptimizationFn for Rosenbrock {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        let x: Vec<f64> = action_vector
+            .iter()
+            .map(|&gene| gene as f64 * 0.001)
+            .collect();
+        x.windows(2)
+            .map(|pair| 100.0 * (pair[1] - pair[0] * pair[0]).powi(2) + (1.0 - pair[0]).powi(2))
+            .sum()
+    }
+}
+
+impl Benchmark for Rosenbrock {
+    fn bounds(&self) -> Vec<(i32, i32)> {
+        vec![(-2048, 2048); self.dimension]
+    }
+
+    fn optimum(&self) -> Vec<i32> {
+        vec![1000; self.dimension]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GmabConfig;
+    use crate::gmab::Gmab;
+
+    #[test]
+    fn test_benchmarks_are_optimal_at_their_optimum() {
+        let sphere = Sphere { dimension: 3 };
+        let rastrigin = Rastrigin { dimension: 3 };
+        let rosenbrock = Rosenbrock { dimension: 3 };
+
+        assert_eq!(sphere.evaluate(&sphere.optimum()), sphere.optimal_fitness());
+        assert!(sphere.evaluate(&[1, 0, 0]) > sphere.optimal_fitness());
+        assert!(
+            (rastrigin.evaluate(&rastrigin.optimum()) - rastrigin.optimal_fitness()).abs() < 1e-12
+        );
+        assert!(rastrigin.evaluate(&[100, 0, 0]) > rastrigin.optimal_fitness());
+        assert!(
+            (rosenbrock.evaluate(&rosenbrock.optimum()) - rosenbrock.optimal_fitness()).abs()
+                < 1e-12
+        );
+        assert!(rosenbrock.evaluate(&[0, 0, 0]) > rosenbrock.optimal_fitness());
+    }
+
+    #[test]
+    fn test_gmab_solves_sphere() {
+        let sphere = Sphere { dimension: 3 };
+        let bounds = sphere.bounds();
+        let optimum = sphere.optimum();
+        let optimal_fitness = sphere.optimal_fitness();
+
+        let config = GmabConfig::new(bounds).with_seed(1);
+        let mut gmab = Gmab::with_config(sphere, config);
+        let best = gmab.optimize(5000);
+
+        let distance: i32 = best
+            .iter()
+            .zip(&optimum)
+            .map(|(x, optimum)| (x - optimum).abs())
+            .sum();
+        assert!(distance <= 3, "best {:?} is far from the optimum", best);
+        assert!(Sphere { dimension: 3 }.evaluate(&best) - optimal_fitness <= 3.0);
+    }
+}
@@ -0,0 +1,867 @@
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::time::Duration;
+
+use crate::arm::Arm;
+use crate::error::GaError;
+use crate::initializer::PopulationInitializer;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+    Minimize,
+    Maximize,
+}
+
+impl Objective {
+    // internally every objective is minimized, the cost of a fitness is its value in that sense
+    pub fn cost(self, fitness: f64) -> f64 {
+        match self {
+            Objective::Minimize => fitness,
+            Objective::Maximize => -fitness,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestartMode {
+    // the population is replaced by random individuals, except for the best one
+    Random,
+    // half of the new individuals lie within `spread` (relative to the range of each
+    // gene) of the best one, the others are random
+    AroundBest {
+        spread: f64,
+    },
+    // mutation_rate and mutation_span are redrawn from the given (min, max) ranges and
+    // `reinit_fraction` of the population is replaced by random individuals
+    Hyperparameters {
+        mutation_rate: (f64, f64),
+        mutation_span: (f64, f64),
+        reinit_fraction: f64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplacementMode {
+    // every offspring of a generation is sampled
+    Generational,
+    // only the first `replace` offspring of a generation are sampled
+    SteadyState { replace: usize },
+    // every offspring is sampled and competes with the most similar parent for its slot
+    DeterministicCrowding,
+}
+
+// preference among arms with equal scores in the bandit selection policies, remaining ties
+// are broken by the lexicographically smallest action vector
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TieBreak {
+    // gathers more information on uncertain arms
+    FewerPulls,
+    // exploits the arms known best
+    MorePulls,
+    LexSmallestAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MutationDistribution {
+    Gaussian,
+    // heavier tails than the gaussian, occasionally producing large exploratory jumps
+    Cauchy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pairing {
+    // population[i] is mated with population[i + 1]
+    Adjacent,
+    // the mating pool is shuffled before adjacent individuals are paired
+    RandomShuffle,
+    // each individual of the better half is mated with a random partner
+    BestWithRandom,
+}
+
+// how crossover treats the individual left without a partner in odd populations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OddCrossover {
+    CloneLeftover,
+    // the leftover is mated with the best individual, producing a single child
+    // with the rounded average of their genes
+    AverageWithBest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParentSelection {
+    // the whole population enters the mating pool
+    Truncation,
+    // parents are drawn proportionally to their (scaled) fitness
+    Roulette,
+    // parents are drawn proportionally to their rank, the best one having the highest weight
+    Rank,
+    // each parent is the best of `tournament_size` randomly drawn competitors
+    Tournament,
+    // parents are drawn with weights exp(-c / t), c being the cost scaled to [0, 1] within
+    // the population; t anneals geometrically from the initial to the final temperature
+    // as the simulation budget is used up
+    Boltzmann {
+        initial_temperature: f64,
+        final_temperature: f64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FitnessScaling {
+    // the best individual is twice as likely to be selected as the worst one
+    Linear,
+    // weights depend on the distance to the mean in standard deviations
+    SigmaScaling,
+    PowerLaw { exponent: f64 },
+}
+
+// reference of fitness-proportionate selection, individuals are weighted by
+// offset minus their cost (the negated fitness when maximizing)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObjectiveOffset {
+    // the worst cost observed so far
+    Auto,
+    Fixed(f64),
+}
+
+pub(crate) type SharedInitializer = Arc<dyn PopulationInitializer + Send + Sync>;
+
+#[derive(Clone)]
+pub(crate) struct InitializerSlot(pub(crate) SharedInitializer);
+
+impl fmt::Debug for InitializerSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PopulationInitializer")
+    }
+}
+
+// number of integer points within the bounds, None if it does not fit into a u128; bounds
+// with the lower above the upper one leave the search space empty
+pub fn search_space_size(lower: &[i32], upper: &[i32]) -> Option<u128> {
+    search_space_size_with_steps(lower, upper, &vec![1; lower.len()])
+}
+
+// number of grid points lower[i] + k * steps[i] within the bounds, see search_space_size
+pub fn search_space_size_with_steps(lower: &[i32], upper: &[i32], steps: &[i32]) -> Option<u128> {
+    lower
+        .iter()
+        .zip(upper)
+        .zip(steps)
+        .map(|((&low, &high), &step)| match high >= low {
+            true => ((i64::from(high) - i64::from(low)) / i64::from(step) + 1) as u128,
+            false => 0,
+        })
+        .try_fold(1u128, |size, values| size.checked_mul(values))
+}
+
+#[derive(Debug, Clone)]
+pub struct GmabConfig {
+    pub(crate) lower_bound: Vec<i32>,
+    pub(crate) upper_bound: Vec<i32>,
+    pub(crate) steps: Option<Vec<i32>>,
+    pub(crate) objective: Objective,
+    pub(crate) population_size: usize,
+    pub(crate) mutation_rate: f64,
+    pub(crate) crossover_rate: f64,
+    pub(crate) mutation_span: f64,
+    pub(crate) fitness_cache: bool,
+    pub(crate) cache_ttl: Option<Duration>,
+    pub(crate) deterministic: bool,
+    pub(crate) simulation_budget: usize,
+    pub(crate) fitness_epsilon: f64,
+    pub(crate) replacement_mode: ReplacementMode,
+    pub(crate) generation_gap: f64,
+    pub(crate) annealing_temperature: Option<f64>,
+    pub(crate) restart_mode: RestartMode,
+    pub(crate) restart_patience: Option<usize>,
+    // period and diversity share of track_shift
+    pub(crate) dynamic_tracking: Option<(usize, f64)>,
+    pub(crate) record_trajectory: bool,
+    pub(crate) mutation_distribution: MutationDistribution,
+    pub(crate) pairing: Pairing,
+    pub(crate) odd_crossover: OddCrossover,
+    pub(crate) parent_selection: ParentSelection,
+    pub(crate) fitness_scaling: Option<FitnessScaling>,
+    // weight and number of nearest neighbours of the novelty bonus
+    pub(crate) novelty: Option<(f64, usize)>,
+    pub(crate) tournament_size: usize,
+    pub(crate) tournament_replacement: bool,
+    pub(crate) tie_break: TieBreak,
+    pub(crate) objective_offset: Option<ObjectiveOffset>,
+    pub(crate) saturation_threshold: Option<f64>,
+    pub(crate) max_resample_attempts: usize,
+    pub(crate) shrink_to_search_space: bool,
+    pub(crate) min_distance: Option<f64>,
+    pub(crate) allow_duplicates: bool,
+    pub(crate) crossover_dedup: bool,
+    pub(crate) initializer: Option<InitializerSlot>,
+    pub(crate) evaluation_timing: bool,
+    pub(crate) catch_panics: bool,
+    pub(crate) local_search: bool,
+    pub(crate) max_simulations_per_generation: Option<usize>,
+    // factor and period of zoom_bounds
+    pub(crate) zoom: Option<(f64, usize)>,
+    pub(crate) batch_evaluation: bool,
+    pub(crate) min_recommendation_pulls: i32,
+    pub(crate) record_trace: bool,
+    pub(crate) record_history: bool,
+    pub(crate) seed: Option<u64>,
+    pub(crate) target_fitness: Option<f64>,
+}
+
+impl GmabConfig {
+    pub fn new(bounds: Vec<(i32, i32)>) -> Self {
+        let lower_bound = bounds.iter().map(|&(low, _)| low).collect::<Vec<i32>>();
+        let upper_bound = bounds.iter().map(|&(_, high)| high).collect::<Vec<i32>>();
+
+        GmabConfig::from_bounds(lower_bound, upper_bound)
+    }
+
+    pub fn from_bounds(lower_bound: Vec<i32>, upper_bound: Vec<i32>) -> Self {
+        GmabConfig {
+            lower_bound,
+            upper_bound,
+            steps: None,
+            objective: Objective::Minimize,
+            population_size: 20, // Default population size
+            mutation_rate: 0.25, // Default mutation rate
+            crossover_rate: 1.0, // Default crossover rate
+            mutation_span: 0.1,  // Default mutation span
+            fitness_cache: false,
+            cache_ttl: None,
+            deterministic: false,
+            simulation_budget: 10000,
+            fitness_epsilon: 0.0,
+            replacement_mode: ReplacementMode::Generational,
+            generation_gap: 1.0,
+            annealing_temperature: None,
+            restart_mode: RestartMode::Random,
+            restart_patience: None,
+            dynamic_tracking: None,
+            record_trajectory: false,
+            mutation_distribution: MutationDistribution::Gaussian,
+            pairing: Pairing::RandomShuffle,
+            odd_crossover: OddCrossover::CloneLeftover,
+            parent_selection: ParentSelection::Truncation,
+            fitness_scaling: None,
+            novelty: None,
+            tournament_size: 2,
+            tournament_replacement: true,
+            tie_break: TieBreak::LexSmallestAction,
+            objective_offset: None,
+            saturation_threshold: None,
+            max_resample_attempts: 10000,
+            shrink_to_search_space: false,
+            min_distance: None,
+            allow_duplicates: false,
+            crossover_dedup: false,
+            initializer: None,
+            evaluation_timing: false,
+            catch_panics: false,
+            local_search: false,
+            max_simulations_per_generation: None,
+            zoom: None,
+            batch_evaluation: false,
+            min_recommendation_pulls: 1,
+            record_trace: false,
+            record_history: false,
+            seed: None,
+            target_fitness: None,
+        }
+    }
+
+    // restricts gene i to the values lower_bound[i] + k * steps[i]
+    pub fn with_steps(mut self, steps: Vec<i32>) -> Self {
+        assert_eq!(
+            steps.len(),
+            self.dimension(),
+            "one step per dimension is required"
+        );
+        assert!(steps.iter().all(|&step| step > 0), "steps must be positive");
+        self.steps = Some(steps);
+        self
+    }
+
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    pub fn with_population_size(mut self, population_size: usize) -> Self {
+        self.population_size = population_size;
+        self
+    }
+
+    pub fn with_mutation_rate(mut self, mutation_rate: f64) -> Self {
+        self.mutation_rate = mutation_rate;
+        self
+    }
+
+    pub fn with_crossover_rate(mut self, crossover_rate: f64) -> Self {
+        self.crossover_rate = crossover_rate;
+        self
+    }
+
+    pub fn with_mutation_span(mut self, mutation_span: f64) -> Self {
+        self.mutation_span = mutation_span;
+        self
+    }
+
+    // Only sound for deterministic functions: repeated evaluations of a cached
+    // action vector return the stored value and are not charged to the budget.
+    pub fn with_fitness_cache(mut self, fitness_cache: bool) -> Self {
+        self.fitness_cache = fitness_cache;
+        self
+    }
+
+    // enables the fitness cache for objectives drifting slowly over time, entries older
+    // than `ttl` are evaluated again; expiry needs std, without it entries never expire
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.fitness_cache = true;
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    // every distinct action vector is simulated at most once, later evaluations are served
+    // by the fitness cache regardless of its setting and time-to-live
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    pub fn with_simulation_budget(mut self, simulation_budget: usize) -> Self {
+        self.simulation_budget = simulation_budget;
+        self
+    }
+
+    // Differences in fitness up to this value are treated as noise rather than progress,
+    // e.g. by stagnation detection, crowding replacement and annealing acceptance.
+    pub fn with_fitness_epsilon(mut self, fitness_epsilon: f64) -> Self {
+        self.fitness_epsilon = fitness_epsilon;
+        self
+    }
+
+    pub fn with_replacement_mode(mut self, replacement_mode: ReplacementMode) -> Self {
+        self.replacement_mode = replacement_mode;
+        self
+    }
+
+    // generational only: below 1.0 each generation samples this share of the population
+    // size as offspring, which take the place of the worst members while the rest is
+    // carried over by fitness; 1.0 ranks offspring and population together as usual
+    pub fn with_generation_gap(mut self, generation_gap: f64) -> Self {
+        assert!(
+            generation_gap > 0.0 && generation_gap <= 1.0,
+            "generation gap must lie in (0, 1]"
+        );
+        self.generation_gap = generation_gap;
+        self
+    }
+
+    // steady-state only: offspring worse than the population are still admitted with the
+    // Metropolis probability exp(-delta / T), T falling linearly from this value to 0
+    // over the budget
+    pub fn with_annealing(mut self, initial_temperature: f64) -> Self {
+        self.annealing_temperature = Some(initial_temperature);
+        self
+    }
+
+    // restarts the population after `patience` generations without improvement
+    pub fn with_restart(mut self, restart_mode: RestartMode, patience: usize) -> Self {
+        if let RestartMode::Hyperparameters {
+            mutation_rate,
+            mutation_span,
+            reinit_fraction,
+        } = restart_mode
+        {
+            for (min, max) in [mutation_rate, mutation_span] {
+                assert!(
+                    min > 0.0 && min <= max && max.is_finite(),
+                    "ranges must be positive and finite with min <= max"
+                );
+            }
+            assert!(mutation_rate.1 <= 1.0, "mutation rate must not exceed 1");
+            assert!(
+                (0.0..=1.0).contains(&reinit_fraction),
+                "reinit fraction must lie in [0, 1]"
+            );
+        }
+        self.restart_mode = restart_mode;
+        self.restart_patience = Some(patience);
+        self
+    }
+
+    // for dynamic objectives: every `period` generations all evaluated arms are forgotten,
+    // the best part of the population is re-evaluated and the `diversity` share of it is
+    // replaced by random individuals
+    pub fn with_dynamic_tracking(mut self, period: usize, diversity: f64) -> Self {
+        assert!(period > 0, "tracking period must be positive");
+        assert!(
+            (0.0..=1.0).contains(&diversity),
+            "diversity must lie in [0, 1]"
+        );
+        self.dynamic_tracking = Some((period, diversity));
+        self
+    }
+
+    // keeps a copy of the recommended arm after every generation
+    pub fn with_record_trajectory(mut self, record_trajectory: bool) -> Self {
+        self.record_trajectory = record_trajectory;
+        self
+    }
+
+    pub fn with_mutation_distribution(
+        mut self,
+        mutation_distribution: MutationDistribution,
+    ) -> Self {
+        self.mutation_distribution = mutation_distribution;
+        self
+    }
+
+    pub fn with_odd_crossover(mut self, odd_crossover: OddCrossover) -> Self {
+        self.odd_crossover = odd_crossover;
+        self
+    }
+
+    pub fn with_pairing(mut self, pairing: Pairing) -> Self {
+        self.pairing = pairing;
+        self
+    }
+
+    pub fn with_parent_selection(mut self, parent_selection: ParentSelection) -> Self {
+        if let ParentSelection::Boltzmann {
+            initial_temperature,
+            final_temperature,
+        } = parent_selection
+        {
+            assert!(
+                initial_temperature > 0.0 && final_temperature > 0.0,
+                "temperatures must be positive"
+            );
+        }
+        self.parent_selection = parent_selection;
+        self
+    }
+
+    // competitors per tournament, larger tournaments increase the selection pressure
+    pub fn with_tournament_size(mut self, tournament_size: usize) -> Self {
+        assert!(tournament_size > 0, "tournament size must be positive");
+        self.tournament_size = tournament_size;
+        self
+    }
+
+    // whether an individual can compete more than once in the same tournament
+    pub fn with_tournament_replacement(mut self, tournament_replacement: bool) -> Self {
+        self.tournament_replacement = tournament_replacement;
+        self
+    }
+
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    // without an offset the worst individual of the population is the reference
+    pub fn with_objective_offset(mut self, objective_offset: ObjectiveOffset) -> Self {
+        self.objective_offset = Some(objective_offset);
+        self
+    }
+
+    // applied to the fitnesses before roulette selection
+    pub fn with_fitness_scaling(mut self, fitness_scaling: FitnessScaling) -> Self {
+        self.fitness_scaling = Some(fitness_scaling);
+        self
+    }
+
+    // parents are selected by their cost minus `weight` times the mean distance to their
+    // `neighbours` nearest individuals; the reported best arm is unaffected
+    pub fn with_novelty(mut self, weight: f64, neighbours: usize) -> Self {
+        assert!(weight >= 0.0, "novelty weight must not be negative");
+        assert!(neighbours > 0, "novelty needs at least one neighbour");
+        self.novelty = Some((weight, neighbours));
+        self
+    }
+
+    // once the share of duplicate candidates in a refill exceeds this value the
+    // mutation span is doubled to escape the saturated neighbourhood, the first refill
+    // below it restores the original span
+    pub fn with_saturation_threshold(mut self, saturation_threshold: f64) -> Self {
+        self.saturation_threshold = Some(saturation_threshold);
+        self
+    }
+
+    // upper limit of rejected candidates while sampling unique individuals
+    pub fn with_max_resample_attempts(mut self, max_resample_attempts: usize) -> Self {
+        self.max_resample_attempts = max_resample_attempts;
+        self
+    }
+
+    // search spaces with fewer points than population_size shrink the population instead of
+    // failing with ResampleExhausted, Gmab::population_shrunk_from reports the original size
+    pub fn with_shrink_to_search_space(mut self, shrink_to_search_space: bool) -> Self {
+        self.shrink_to_search_space = shrink_to_search_space;
+        self
+    }
+
+    // new individuals closer than min_distance (by the distance metric) to an individual
+    // already in the same population are rejected like duplicates
+    pub fn with_min_distance(mut self, min_distance: f64) -> Self {
+        self.min_distance = Some(min_distance);
+        self
+    }
+
+    // generates the initial population and the random individuals of restarts
+    pub fn with_initializer(
+        mut self,
+        initializer: impl PopulationInitializer + Send + Sync + 'static,
+    ) -> Self {
+        self.initializer = Some(InitializerSlot(Arc::new(initializer)));
+        self
+    }
+
+    // repeated crossover children are replaced by random individuals
+    pub fn with_crossover_dedup(mut self, crossover_dedup: bool) -> Self {
+        self.crossover_dedup = crossover_dedup;
+        self
+    }
+
+    // duplicate individuals are kept by the genetic operators; Gmab populations consist of
+    // distinct arms, a duplicate adds a pull to its arm instead of taking a slot, so the
+    // population is smaller while fewer distinct arms have been evaluated
+    pub fn with_allow_duplicates(mut self, allow_duplicates: bool) -> Self {
+        self.allow_duplicates = allow_duplicates;
+        self
+    }
+
+    // the simulations of a generation are handed to the optimization function in a single
+    // evaluate_batch call
+    pub fn with_batch_evaluation(mut self, batch_evaluation: bool) -> Self {
+        self.batch_evaluation = batch_evaluation;
+        self
+    }
+
+    // every `period` generations the search bounds shrink to `factor` of their width
+    // around the best arm
+    pub fn with_zoom(mut self, factor: f64, period: usize) -> Self {
+        assert!(
+            factor > 0.0 && factor <= 1.0,
+            "zoom factor must lie in (0, 1]"
+        );
+        assert!(period > 0, "zoom period must be positive");
+        self.zoom = Some((factor, period));
+        self
+    }
+
+    // limits the simulations of a single generation; offspring are sampled first, the rest
+    // of the cap is spread over the population by an optimistic bandit policy
+    pub fn with_max_simulations_per_generation(mut self, max_simulations: usize) -> Self {
+        self.max_simulations_per_generation = Some(max_simulations);
+        self
+    }
+
+    // hill-climbs from the best arm after every generation
+    pub fn with_local_search(mut self, local_search: bool) -> Self {
+        self.local_search = local_search;
+        self
+    }
+
+    // measures the duration of every simulation, only available with std
+    pub fn with_evaluation_timing(mut self, evaluation_timing: bool) -> Self {
+        self.evaluation_timing = evaluation_timing;
+        self
+    }
+
+    // a panicking objective function gives the arm the worst possible fitness instead of
+    // aborting the run, also in batch evaluation; the culprits are kept in
+    // Gmab::panicked_inputs, only available with std
+    pub fn with_catch_panics(mut self, catch_panics: bool) -> Self {
+        self.catch_panics = catch_panics;
+        self
+    }
+
+    // arms with fewer pulls are not trusted enough to be recommended by get_best_k_distinct
+    pub fn with_min_recommendation_pulls(mut self, min_recommendation_pulls: i32) -> Self {
+        self.min_recommendation_pulls = min_recommendation_pulls;
+        self
+    }
+
+    // records the rewards of every evaluation so that the run can be replayed, runs without
+    // a seed get a random one
+    pub fn with_record_trace(mut self, record_trace: bool) -> Self {
+        self.record_trace = record_trace;
+        self
+    }
+
+    // keeps an EvaluationRecord of every evaluation, see write_history_jsonl
+    pub fn with_record_history(mut self, record_history: bool) -> Self {
+        self.record_history = record_history;
+        self
+    }
+
+    // without a seed the random number generator is seeded from system entropy
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    // a run counts as successful once its best fitness reaches this value
+    pub fn with_target_fitness(mut self, target_fitness: f64) -> Self {
+        self.target_fitness = Some(target_fitness);
+        self
+    }
+
+    // builds an arm that is guaranteed to lie within the search space
+    pub fn build_arm(&self, action_vector: Vec<i32>) -> Result<Arm, GaError> {
+        if !action_vector.is_empty() && action_vector.len() != self.dimension() {
+            return Err(GaError::DimensionMismatch {
+                expected: self.dimension(),
+                actual: action_vector.len(),
+            });
+        }
+
+        for (index, &value) in action_vector.iter().enumerate() {
+            let (lower, upper) = (self.lower_bound[index], self.upper_bound[index]);
+            if value < lower || value > upper {
+                return Err(GaError::OutOfBounds {
+                    index,
+                    value,
+                    lower,
+                    upper,
+                });
+            }
+            if let Some(steps) = &self.steps {
+                if (value - lower) % steps[index] != 0 {
+                    return Err(GaError::OffGrid {
+                        index,
+                        value,
+                        step: steps[index],
+                    });
+                }
+            }
+        }
+
+        Arm::try_from(action_vector)
+    }
+
+    // every problem of the configuration instead of only the first
+    pub fn validate_config(&self) -> Result<(), Vec<GaError>> {
+        let mut errors = Vec::new();
+
+        if self.lower_bound.is_empty() || self.upper_bound.is_empty() {
+            errors.push(GaError::EmptySearchSpace);
+        }
+        if self.upper_bound.len() != self.lower_bound.len() {
+            errors.push(GaError::DimensionMismatch {
+                expected: self.lower_bound.len(),
+                actual: self.upper_bound.len(),
+            });
+        }
+        for (index, (&lower, &upper)) in self.lower_bound.iter().zip(&self.upper_bound).enumerate()
+        {
+            if lower > upper {
+                errors.push(GaError::InvalidBounds {
+                    index,
+                    lower,
+                    upper,
+                });
+            }
+        }
+        if self.population_size < 2 {
+            errors.push(GaError::PopulationTooSmall {
+                size: self.population_size,
+            });
+        }
+
+        let rates = [
+            ("mutation_rate", self.mutation_rate),
+            ("crossover_rate", self.crossover_rate),
+        ];
+        for (name, value) in rates {
+            if !(0.0..=1.0).contains(&value) {
+                errors.push(GaError::InvalidParameter { name, value });
+            }
+        }
+        if self.mutation_span.is_nan() || self.mutation_span <= 0.0 {
+            errors.push(GaError::InvalidParameter {
+                name: "mutation_span",
+                value: self.mutation_span,
+            });
+        }
+        if self.fitness_epsilon.is_nan() || self.fitness_epsilon < 0.0 {
+            errors.push(GaError::InvalidParameter {
+                name: "fitness_epsilon",
+                value: self.fitness_epsilon,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub(crate) fn dimension(&self) -> usize {
+        self.lower_bound.len()
+    }
+
+    pub(crate) fn caches_fitness(&self) -> bool {
+        self.fitness_cache || self.deterministic
+    }
+
+    // deterministic results never go stale
+    pub(crate) fn effective_cache_ttl(&self) -> Option<Duration> {
+        self.cache_ttl.filter(|_| !self.deterministic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_build_arm_enforces_bounds() {
+        let config = GmabConfig::new(vec![(0, 10), (-5, 5)]);
+
+        assert_eq!(
+            config.build_arm(vec![3, -5]).unwrap().get_action_vector(),
+            &[3, -5]
+        );
+        assert_eq!(config.build_arm(vec![]), Err(GaError::EmptyActionVector));
+        assert_eq!(
+            config.build_arm(vec![3]),
+            Err(GaError::DimensionMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            config.build_arm(vec![11, 0]),
+            Err(GaError::OutOfBounds {
+                index: 0,
+                value: 11,
+                lower: 0,
+                upper: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_config_reports_every_problem() {
+        assert_eq!(
+            GmabConfig::new(vec![(0, 10), (-5, 5)]).validate_config(),
+            Ok(())
+        );
+
+        let config = GmabConfig::from_bounds(vec![0, 8, 3], vec![10, 2])
+            .with_population_size(1)
+            .with_mutation_rate(1.5)
+            .with_crossover_rate(-0.1)
+            .with_mutation_span(0.0);
+        assert_eq!(
+            config.validate_config(),
+            Err(vec![
+                GaError::DimensionMismatch {
+                    expected: 3,
+                    actual: 2,
+                },
+                GaError::InvalidBounds {
+                    index: 1,
+                    lower: 8,
+                    upper: 2,
+                },
+                GaError::PopulationTooSmall { size: 1 },
+                GaError::InvalidParameter {
+                    name: "mutation_rate",
+                    value: 1.5,
+                },
+                GaError::InvalidParameter {
+                    name: "crossover_rate",
+                    value: -0.1,
+                },
+                GaError::InvalidParameter {
+                    name: "mutation_span",
+                    value: 0.0,
+                },
+            ])
+        );
+
+        assert_eq!(
+            GmabConfig::new(vec![]).validate_config(),
+            Err(vec![GaError::EmptySearchSpace])
+        );
+    }
+
+    #[test]
+    fn test_search_space_size() {
+        assert_eq!(search_space_size(&[0, -2], &[3, 2]), Some(20));
+        // a fixed dimension has a single value
+        assert_eq!(search_space_size(&[0, 7, 1], &[9, 7, 2]), Some(20));
+        assert_eq!(search_space_size(&[i32::MIN], &[i32::MAX]), Some(1 << 32));
+
+        // (2^32)^4 = 2^128 does not fit
+        assert_eq!(
+            search_space_size(&[i32::MIN; 3], &[i32::MAX; 3]),
+            Some(1 << 96)
+        );
+        assert_eq!(search_space_size(&[i32::MIN; 4], &[i32::MAX; 4]), None);
+        assert_eq!(search_space_size(&[0, 3], &[4, 2]), Some(0));
+    }
+
+    #[test]
+    fn test_search_space_size_with_steps() {
+        // 0, 3, 6, 9 and -2, 3
+        assert_eq!(
+            search_space_size_with_steps(&[0, -2], &[10, 4], &[3, 5]),
+            Some(8)
+        );
+        assert_eq!(
+            search_space_size_with_steps(&[i32::MIN], &[i32::MAX], &[i32::MAX]),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_build_arm_enforces_steps() {
+        let config = GmabConfig::new(vec![(1, 21)]).with_steps(vec![5]);
+
+        assert!(config.build_arm(vec![16]).is_ok());
+        assert_eq!(
+            config.build_arm(vec![15]),
+            Err(GaError::OffGrid {
+                index: 0,
+                value: 15,
+                step: 5
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ranges must be positive and finite with min <= max")]
+    fn test_restart_rejects_inverted_range() {
+        GmabConfig::new(vec![(0, 10)]).with_restart(
+            RestartMode::Hyperparameters {
+                mutation_rate: (0.6, 0.3),
+                mutation_span: (0.2, 0.4),
+                reinit_fraction: 0.5,
+            },
+            1,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "reinit fraction must lie in [0, 1]")]
+    fn test_restart_rejects_nan_reinit_fraction() {
+        GmabConfig::new(vec![(0, 10)]).with_restart(
+            RestartMode::Hyperparameters {
+                mutation_rate: (0.3, 0.6),
+                mutation_span: (0.2, 0.4),
+                reinit_fraction: f64::NAN,
+            },
+            1,
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use core::error::Error;
+use core::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GaError {
+    EmptyActionVector,
+    DimensionMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    OutOfBounds {
+        index: usize,
+        value: i32,
+        lower: i32,
+        upper: i32,
+    },
+    OffGrid {
+        index: usize,
+        value: i32,
+        step: i32,
+    },
+    InvalidBounds {
+        index: usize,
+        lower: i32,
+        upper: i32,
+    },
+    ResampleExhausted {
+        attempts: usize,
+    },
+    PopulationTooSmall {
+        size: usize,
+    },
+    InvalidParameter {
+        name: &'static str,
+        value: f64,
+    },
+    EmptySearchSpace,
+    InvalidTrace,
+    TraceConfigMismatch,
+    AlreadyEvaluated,
+    TooManyProtectedArms {
+        population_size: usize,
+    },
+    BudgetExhausted {
+        budget: usize,
+    },
+}
+
+impl fmt::Display for GaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GaError::EmptyActionVector => write!(f, "action vector must not be empty"),
+            GaError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "action vector has {} genes, expected {}",
+                actual, expected
+            ),
+            GaError::OutOfBounds {
+                index,
+                value,
+                lower,
+                upper,
+            } => write!(
+                f,
+                "gene {} with value {} is outside of [{}, {}]",
+                index, value, lower, upper
+            ),
+            GaError::OffGrid { index, value, step } => write!(
+                f,
+                "gene {} with value {} is not on the grid with step {}",
+                index, value, step
+            ),
+            GaError::InvalidBounds {
+                index,
+                lower,
+                upper,
+            } => write!(
+                f,
+                "gene {} has a lower bound {} above its upper bound {}",
+                index, lower, upper
+            ),
+            GaError::ResampleExhausted { attempts } => write!(
+                f,
+                "no new unique individual found after {} attempts",
+                attempts
+            ),
+            GaError::PopulationTooSmall { size } => write!(
+                f,
+                "population of {} individuals is too small, at least 2 are required",
+                size
+            ),
+            GaError::InvalidParameter { name, value } => {
+                write!(f, "{} has the invalid value {}", name, value)
+            }
+            GaError::EmptySearchSpace => write!(f, "search space has no dimensions"),
+            GaError::InvalidTrace => write!(f, "run trace could not be parsed"),
+            GaError::TraceConfigMismatch => {
+                write!(f, "run trace was recorded with a different configuration")
+            }
+            GaError::AlreadyEvaluated => {
+                write!(f, "initial population has already been evaluated")
+            }
+            GaError::TooManyProtectedArms { population_size } => write!(
+                f,
+                "protected arms must leave at least two of the {} individuals for breeding",
+                population_size
+            ),
+            GaError::BudgetExhausted { budget } => {
+                write!(f, "simulation budget of {} is exhausted", budget)
+            }
+        }
+    }
+}
+
+impl Error for GaError {}
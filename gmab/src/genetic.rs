@@ -1,22 +1,86 @@
-use std::collections::HashSet;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
 
-use rand::Rng;
-use rand_distr::{Distribution, Normal};
+use rand::distributions::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_distr::{Cauchy, Distribution, Normal};
 
 use crate::arm::{Arm, OptimizationFn};
+use crate::config::{
+    search_space_size_with_steps, FitnessScaling, MutationDistribution, OddCrossover, Pairing,
+    ParentSelection, SharedInitializer,
+};
+use crate::error::GaError;
+use crate::selection::{
+    boltzmann_probabilities, rank_probabilities, roulette_probabilities, tournament_probabilities,
+};
+use crate::Set;
+
+pub(crate) type ActivityMask = Box<dyn Fn(&[i32]) -> Vec<bool> + Send + Sync>;
+pub(crate) type DistanceMetric = Box<dyn Fn(&[i32], &[i32]) -> f64 + Send + Sync>;
+pub(crate) type Perturbation = Box<dyn Fn(f64, &mut dyn RngCore) -> f64 + Send + Sync>;
+
+#[cfg(feature = "std")]
+fn default_rng() -> StdRng {
+    StdRng::from_entropy()
+}
+
+// there is no entropy source without std, runs are seeded with 0 unless a seed is configured
+#[cfg(not(feature = "std"))]
+fn default_rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
 
 pub(crate) struct GeneticAlgorithm<F: OptimizationFn> {
     mutation_rate: f64,
     crossover_rate: f64,
     mutation_span: f64,
+    // standard deviation of the mutation of each gene in value units, replaces mutation_span
+    absolute_mutation_sigma: Option<Vec<f64>>,
     pub(crate) population_size: usize,
     pub(crate) opti_function: F,
     dimension: usize,
     lower_bound: Vec<i32>,
     upper_bound: Vec<i32>,
+    // genes only take the values lower_bound + k * steps
+    steps: Vec<i32>,
+    frozen_genes: Vec<Option<i32>>,
+    activity_mask: Option<ActivityMask>,
+    // None measures the euclidean distance
+    distance_metric: Option<DistanceMetric>,
+    min_distance: Option<f64>,
+    allow_duplicates: bool,
+    crossover_dedup: bool,
+    initializer: Option<SharedInitializer>,
+    mutation_distribution: MutationDistribution,
+    perturbation: Option<Perturbation>,
+    pairing: Pairing,
+    odd_crossover: OddCrossover,
+    parent_selection: ParentSelection,
+    fitness_scaling: Option<FitnessScaling>,
+    tournament_size: usize,
+    tournament_replacement: bool,
+    selection_temperature: f64,
+    // cost relative to which roulette selection weights individuals
+    selection_reference: Option<f64>,
+    saturation_threshold: Option<f64>,
+    // duplicate candidates and total candidates of the last refill
+    collisions: usize,
+    candidates: usize,
+    saturation_responses: usize,
+    // mutation span before saturation expanded it, restored once the collisions subside
+    unsaturated_span: Option<f64>,
+    max_resample_attempts: usize,
+    pub(crate) rng: StdRng,
 }
 
 impl<F: OptimizationFn> GeneticAlgorithm<F> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         opti_function: F,
         population_size: usize,
@@ -31,110 +95,814 @@ impl<F: OptimizationFn> GeneticAlgorithm<F> {
             mutation_rate,
             crossover_rate,
             mutation_span,
+            absolute_mutation_sigma: None,
             population_size,
             opti_function,
             dimension,
             lower_bound,
             upper_bound,
+            steps: vec![1; dimension],
+            frozen_genes: vec![None; dimension],
+            activity_mask: None,
+            distance_metric: None,
+            min_distance: None,
+            allow_duplicates: false,
+            crossover_dedup: false,
+            initializer: None,
+            mutation_distribution: MutationDistribution::Gaussian,
+            perturbation: None,
+            pairing: Pairing::Adjacent,
+            odd_crossover: OddCrossover::CloneLeftover,
+            parent_selection: ParentSelection::Truncation,
+            fitness_scaling: None,
+            tournament_size: 2,
+            tournament_replacement: true,
+            selection_temperature: 1.0,
+            selection_reference: None,
+            saturation_threshold: None,
+            collisions: 0,
+            candidates: 0,
+            saturation_responses: 0,
+            unsaturated_span: None,
+            max_resample_attempts: 10000,
+            rng: default_rng(),
+        }
+    }
+
+    pub(crate) fn bounds(&self) -> Vec<(i32, i32)> {
+        self.lower_bound
+            .iter()
+            .copied()
+            .zip(self.upper_bound.iter().copied())
+            .collect()
+    }
+
+    // narrows the bounds to `factor` of their current width around `center`, keeping at
+    // least one grid step per gene and staying inside `limits`; the grid is left unchanged
+    pub(crate) fn zoom(&mut self, center: &Arm, factor: f64, limits: &[(i32, i32)]) {
+        for (j, &(limit_lower, limit_upper)) in limits.iter().enumerate() {
+            let step = i64::from(self.steps[j]);
+            let cells = self.grid_size(j);
+            if cells == 0 {
+                continue;
+            }
+            let new_cells = ((cells as f64 * factor).round() as i64).clamp(1, cells);
+
+            let (limit_lower, limit_upper) = (i64::from(limit_lower), i64::from(limit_upper));
+            let top = limit_lower + (limit_upper - limit_lower) / step * step;
+            let mut lower = i64::from(center.get_action_vector()[j]) - new_cells / 2 * step;
+            let mut upper = lower + new_cells * step;
+            if lower < limit_lower {
+                lower = limit_lower;
+                upper = lower + new_cells * step;
+            }
+            if upper > top {
+                upper = top;
+                lower = upper - new_cells * step;
+            }
+            // both lie within the limits
+            self.lower_bound[j] = lower as i32;
+            self.upper_bound[j] = upper as i32;
+        }
+    }
+
+    pub(crate) fn set_bounds(&mut self, lower_bound: Vec<i32>, upper_bound: Vec<i32>) {
+        self.lower_bound = lower_bound;
+        self.upper_bound = upper_bound;
+    }
+
+    pub(crate) fn set_steps(&mut self, steps: Vec<i32>) {
+        self.steps = steps;
+    }
+
+    // widths and grid positions are computed in i64, bounds near i32::MIN and i32::MAX would
+    // overflow in i32
+    fn range(&self, j: usize) -> i64 {
+        i64::from(self.upper_bound[j]) - i64::from(self.lower_bound[j])
+    }
+
+    fn grid_size(&self, j: usize) -> i64 {
+        self.range(j) / i64::from(self.steps[j])
+    }
+
+    // lower bound plus k grid steps, k must not exceed the grid size
+    fn grid_value(&self, j: usize, k: i64) -> i32 {
+        (i64::from(self.lower_bound[j]) + k * i64::from(self.steps[j])) as i32
+    }
+
+    // number of distinct individuals, saturating at usize::MAX; a frozen gene only takes
+    // its pinned value
+    pub(crate) fn search_space_size(&self) -> usize {
+        let (lower, upper): (Vec<i32>, Vec<i32>) = (0..self.dimension)
+            .map(|j| match self.frozen_genes[j] {
+                Some(value) => (value, value),
+                None => (self.lower_bound[j], self.upper_bound[j]),
+            })
+            .unzip();
+        search_space_size_with_steps(&lower, &upper, &self.steps)
+            .and_then(|size| usize::try_from(size).ok())
+            .unwrap_or(usize::MAX)
+    }
+
+    // nearest grid value of gene j within the bounds
+    pub(crate) fn snap_to_grid(&self, j: usize, value: f64) -> i32 {
+        let step = self.steps[j] as f64;
+        let k = ((value - self.lower_bound[j] as f64) / step)
+            .round()
+            .clamp(0.0, self.grid_size(j) as f64);
+
+        self.grid_value(j, k as i64)
+    }
+
+    pub(crate) fn freeze_gene(&mut self, index: usize, value: i32) {
+        assert!(
+            value >= self.lower_bound[index] && value <= self.upper_bound[index],
+            "frozen value must lie within the bounds"
+        );
+        self.frozen_genes[index] = Some(value);
+    }
+
+    pub(crate) fn unfreeze_gene(&mut self, index: usize) {
+        self.frozen_genes[index] = None;
+    }
+
+    // overwrites frozen genes with their pinned value
+    fn pin_frozen_genes(&self, mut genes: Vec<i32>) -> Vec<i32> {
+        for (gene, frozen_value) in genes.iter_mut().zip(&self.frozen_genes) {
+            if let Some(frozen_value) = frozen_value {
+                *gene = *frozen_value;
+            }
+        }
+        genes
+    }
+
+    pub(crate) fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub(crate) fn set_mutation_distribution(
+        &mut self,
+        mutation_distribution: MutationDistribution,
+    ) {
+        self.mutation_distribution = mutation_distribution;
+    }
+
+    pub(crate) fn set_pairing(&mut self, pairing: Pairing) {
+        self.pairing = pairing;
+    }
+
+    pub(crate) fn set_odd_crossover(&mut self, odd_crossover: OddCrossover) {
+        self.odd_crossover = odd_crossover;
+    }
+
+    pub(crate) fn set_parent_selection(
+        &mut self,
+        parent_selection: ParentSelection,
+        fitness_scaling: Option<FitnessScaling>,
+    ) {
+        self.parent_selection = parent_selection;
+        self.fitness_scaling = fitness_scaling;
+    }
+
+    pub(crate) fn set_tournament(&mut self, tournament_size: usize, tournament_replacement: bool) {
+        self.tournament_size = tournament_size;
+        self.tournament_replacement = tournament_replacement;
+    }
+
+    pub(crate) fn set_selection_temperature(&mut self, selection_temperature: f64) {
+        self.selection_temperature = selection_temperature;
+    }
+
+    pub(crate) fn set_selection_reference(&mut self, selection_reference: Option<f64>) {
+        self.selection_reference = selection_reference;
+    }
+
+    // probability of each individual to be drawn as a parent, uniform for truncation
+    pub(crate) fn selection_probabilities(
+        &self,
+        population: &[Arm],
+        fitnesses: &[f64],
+    ) -> Vec<f64> {
+        match self.parent_selection {
+            ParentSelection::Truncation => vec![1.0 / population.len() as f64; population.len()],
+            ParentSelection::Roulette => {
+                roulette_probabilities(fitnesses, self.selection_reference, self.fitness_scaling)
+            }
+            ParentSelection::Rank => rank_probabilities(fitnesses, population),
+            ParentSelection::Tournament => tournament_probabilities(
+                fitnesses,
+                population,
+                self.tournament_size,
+                self.tournament_replacement,
+            ),
+            ParentSelection::Boltzmann { .. } => {
+                boltzmann_probabilities(fitnesses, self.selection_temperature)
+            }
+        }
+    }
+
+    // draws the parents of the next generation (with replacement) from the population,
+    // `fitnesses` holding the fitness of each individual (lower is better)
+    pub(crate) fn select_mating_pool(&mut self, population: &[Arm], fitnesses: &[f64]) -> Vec<Arm> {
+        if self.parent_selection == ParentSelection::Truncation {
+            return population.to_vec();
+        }
+        let probabilities = self.selection_probabilities(population, fitnesses);
+
+        let parent_distribution = WeightedIndex::new(&probabilities).unwrap();
+        (0..population.len())
+            .map(|_| population[parent_distribution.sample(&mut self.rng)].clone())
+            .collect()
+    }
+
+    // indexes of the parents mated in crossover, the population is expected to be
+    // ordered from best to worst; in odd populations the worst individual is not paired
+    // by Adjacent and RandomShuffle
+    fn mating_pairs(&mut self, population_size: usize) -> Vec<(usize, usize)> {
+        let paired = population_size - population_size % 2;
+        match self.pairing {
+            Pairing::Adjacent => (0..paired).step_by(2).map(|i| (i, i + 1)).collect(),
+            Pairing::RandomShuffle => {
+                let mut indexes: Vec<usize> = (0..paired).collect();
+                indexes.shuffle(&mut self.rng);
+                indexes.chunks(2).map(|pair| (pair[0], pair[1])).collect()
+            }
+            Pairing::BestWithRandom => (0..population_size / 2)
+                .map(|i| {
+                    // any partner except the individual itself
+                    let partner = self.rng.gen_range(0..population_size - 1);
+                    (i, if partner >= i { partner + 1 } else { partner })
+                })
+                .collect(),
+        }
+    }
+
+    // replaces the mutation distribution, receives the mutation scale of the gene
+    pub(crate) fn set_perturbation(&mut self, perturbation: Perturbation) {
+        self.perturbation = Some(perturbation);
+    }
+
+    fn sample_adjustment(&mut self, scale: f64) -> f64 {
+        // a dimension without range cannot be perturbed
+        if scale <= 0.0 {
+            return 0.0;
+        }
+
+        if let Some(perturbation) = &self.perturbation {
+            return perturbation(scale, &mut self.rng);
+        }
+
+        match self.mutation_distribution {
+            MutationDistribution::Gaussian => {
+                Normal::new(0.0, scale).unwrap().sample(&mut self.rng)
+            }
+            MutationDistribution::Cauchy => Cauchy::new(0.0, scale).unwrap().sample(&mut self.rng),
+        }
+    }
+
+    pub(crate) fn set_max_resample_attempts(&mut self, max_resample_attempts: usize) {
+        self.max_resample_attempts = max_resample_attempts;
+    }
+
+    pub(crate) fn set_saturation_threshold(&mut self, saturation_threshold: Option<f64>) {
+        self.saturation_threshold = saturation_threshold;
+    }
+
+    // share of duplicate candidates produced by the last population refill
+    pub(crate) fn collision_rate(&self) -> f64 {
+        if self.candidates == 0 {
+            return 0.0;
+        }
+        self.collisions as f64 / self.candidates as f64
+    }
+
+    pub(crate) fn get_saturation_responses(&self) -> usize {
+        self.saturation_responses
+    }
+
+    pub(crate) fn set_mutation_rate(&mut self, mutation_rate: f64) {
+        self.mutation_rate = mutation_rate;
+    }
+
+    pub(crate) fn get_mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    pub(crate) fn set_mutation_span(&mut self, mutation_span: f64) {
+        self.mutation_span = mutation_span;
+        self.unsaturated_span = None;
+    }
+
+    pub(crate) fn get_mutation_span(&self) -> f64 {
+        self.mutation_span
+    }
+
+    pub(crate) fn set_absolute_mutation_sigma(&mut self, sigma: Vec<f64>) {
+        self.absolute_mutation_sigma = Some(sigma);
+    }
+
+    // scale of the mutation of gene j in value units
+    fn mutation_sigma(&self, j: usize) -> f64 {
+        match &self.absolute_mutation_sigma {
+            Some(sigma) => sigma[j],
+            None => self.mutation_span * self.range(j) as f64,
+        }
+    }
+
+    pub(crate) fn effective_mutation_sigma(&self) -> Vec<f64> {
+        (0..self.dimension)
+            .map(|j| self.mutation_sigma(j))
+            .collect()
+    }
+
+    fn record_refill(&mut self, collisions: usize, candidates: usize) {
+        self.collisions = collisions;
+        self.candidates = candidates;
+
+        if let Some(saturation_threshold) = self.saturation_threshold {
+            if self.collision_rate() > saturation_threshold {
+                self.unsaturated_span.get_or_insert(self.mutation_span);
+                self.mutation_span = (self.mutation_span * 2.0).min(1.0);
+                self.saturation_responses += 1;
+            } else if let Some(unsaturated_span) = self.unsaturated_span.take() {
+                self.mutation_span = unsaturated_span;
+            }
+        }
+    }
+
+    pub(crate) fn set_distance_metric(&mut self, distance_metric: DistanceMetric) {
+        self.distance_metric = Some(distance_metric);
+    }
+
+    pub(crate) fn set_min_distance(&mut self, min_distance: Option<f64>) {
+        self.min_distance = min_distance;
+    }
+
+    pub(crate) fn set_initializer(&mut self, initializer: Option<SharedInitializer>) {
+        self.initializer = initializer;
+    }
+
+    pub(crate) fn set_crossover_dedup(&mut self, crossover_dedup: bool) {
+        self.crossover_dedup = crossover_dedup;
+    }
+
+    pub(crate) fn set_allow_duplicates(&mut self, allow_duplicates: bool) {
+        self.allow_duplicates = allow_duplicates;
+    }
+
+    // whether `candidate` duplicates one of `individuals`, or lies closer than min_distance
+    fn is_crowded(&self, candidate: &Arm, individuals: &[Arm]) -> bool {
+        if self.allow_duplicates {
+            return false;
+        }
+        match self.min_distance {
+            Some(min_distance) => individuals
+                .iter()
+                .any(|individual| self.distance(candidate, individual) < min_distance),
+            None => individuals.contains(candidate),
+        }
+    }
+
+    pub(crate) fn distance(&self, a: &Arm, b: &Arm) -> f64 {
+        match &self.distance_metric {
+            Some(distance_metric) => distance_metric(a.get_action_vector(), b.get_action_vector()),
+            None => a.euclidean_distance(b),
+        }
+    }
+
+    // mean pairwise distance between the individuals
+    pub(crate) fn diversity(&self, population: &[Arm]) -> f64 {
+        let mut total_distance = 0.0;
+        let mut pairs = 0;
+
+        for (i, a) in population.iter().enumerate() {
+            for b in &population[i + 1..] {
+                total_distance += self.distance(a, b);
+                pairs += 1;
+            }
         }
+
+        if pairs == 0 {
+            return 0.0;
+        }
+        total_distance / pairs as f64
+    }
+
+    pub(crate) fn set_activity_mask(&mut self, activity_mask: ActivityMask) {
+        self.activity_mask = Some(activity_mask);
+    }
+
+    // derives the active genes of an arm from its action vector, e.g. a flag gene
+    // switching a dependent gene on or off, and sets the inactive ones to their canonical
+    // value so arms differing only in inactive genes are the same arm
+    fn apply_activity_mask(&self, arm: Arm) -> Arm {
+        let Some(activity_mask) = &self.activity_mask else {
+            return arm;
+        };
+        let active_mask = activity_mask(arm.get_action_vector());
+        let genes = self.canonical_genes(arm.get_action_vector(), &active_mask);
+        if genes.as_slice() == arm.get_action_vector() {
+            arm.with_active_mask(active_mask)
+        } else {
+            Arm::new(&genes).with_active_mask(active_mask)
+        }
+    }
+
+    // the action vector with inactive genes at their lower bound, frozen genes keep their
+    // value
+    pub(crate) fn canonical_action_vector(&self, action_vector: &[i32]) -> Vec<i32> {
+        match &self.activity_mask {
+            Some(activity_mask) => {
+                self.canonical_genes(action_vector, &activity_mask(action_vector))
+            }
+            None => action_vector.to_vec(),
+        }
+    }
+
+    fn canonical_genes(&self, action_vector: &[i32], active_mask: &[bool]) -> Vec<i32> {
+        let genes = action_vector
+            .iter()
+            .zip(active_mask)
+            .zip(&self.lower_bound)
+            .map(|((&gene, &active), &lower)| if active { gene } else { lower })
+            .collect();
+        self.pin_frozen_genes(genes)
+    }
+
+    // takes the genes before `cut` from `head` and the remaining ones from `tail`,
+    // except for genes that are inactive in either parent
+    fn cross_genes(&self, head: &Arm, tail: &Arm, cut: usize) -> Arm {
+        let child: Vec<i32> = (0..self.dimension)
+            .map(|j| {
+                if j >= cut && head.is_gene_active(j) && tail.is_gene_active(j) {
+                    tail.get_action_vector()[j]
+                } else {
+                    head.get_action_vector()[j]
+                }
+            })
+            .collect();
+
+        self.apply_activity_mask(Arm::new(&self.pin_frozen_genes(child)))
+    }
+
+    // rounded average of both parents, genes inactive in either parent are taken from `head`
+    fn average_genes(&self, head: &Arm, tail: &Arm) -> Arm {
+        let child: Vec<i32> = (0..self.dimension)
+            .map(|j| {
+                if head.is_gene_active(j) && tail.is_gene_active(j) {
+                    let sum =
+                        head.get_action_vector()[j] as f64 + tail.get_action_vector()[j] as f64;
+                    self.snap_to_grid(j, sum / 2.0)
+                } else {
+                    head.get_action_vector()[j]
+                }
+            })
+            .collect();
+
+        self.apply_activity_mask(Arm::new(&self.pin_frozen_genes(child)))
+    }
+
+    // uniformly drawn grid point, frozen genes pinned
+    pub(crate) fn random_individual(&mut self) -> Arm {
+        let candidate_solution: Vec<i32> = (0..self.dimension)
+            .map(|j| {
+                let k = self.rng.gen_range(0..=self.grid_size(j));
+                self.grid_value(j, k)
+            })
+            .collect();
+
+        let candidate_solution = self.pin_frozen_genes(candidate_solution);
+        self.apply_activity_mask(Arm::new(&candidate_solution))
+    }
+
+    // drops repeated children and tops the offspring up with random individuals, giving up
+    // after max_resample_attempts rejected candidates
+    fn dedup_offspring(
+        &mut self,
+        offspring: Vec<Arm>,
+        crossed: Vec<bool>,
+    ) -> (Vec<Arm>, Vec<bool>) {
+        let target_size = offspring.len();
+        let mut seen = Set::new();
+        let (mut unique, mut unique_crossed): (Vec<Arm>, Vec<bool>) = offspring
+            .into_iter()
+            .zip(crossed)
+            .filter(|(child, _)| seen.insert(child.get_action_vector().to_vec()))
+            .unzip();
+
+        let mut collisions = 0;
+        while unique.len() < target_size && collisions < self.max_resample_attempts {
+            let candidate = self.random_individual();
+            if seen.insert(candidate.get_action_vector().to_vec()) {
+                unique.push(candidate);
+                unique_crossed.push(false);
+            } else {
+                collisions += 1;
+            }
+        }
+        (unique, unique_crossed)
+    }
+
+    // individuals proposed by the initializer, moved onto the grid
+    fn initial_candidates(&mut self) -> Vec<Arm> {
+        let Some(initializer) = self.initializer.clone() else {
+            return Vec::new();
+        };
+        let bounds: Vec<(i32, i32)> = self
+            .lower_bound
+            .iter()
+            .copied()
+            .zip(self.upper_bound.iter().copied())
+            .collect();
+
+        initializer
+            .init(self.population_size, &bounds, &mut self.rng)
+            .iter()
+            .filter(|arm| arm.get_action_vector().len() == self.dimension)
+            .map(|arm| {
+                let genes: Vec<i32> = (0..self.dimension)
+                    .map(|j| self.snap_to_grid(j, arm.get_action_vector()[j] as f64))
+                    .collect();
+                self.apply_activity_mask(Arm::new(&self.pin_frozen_genes(genes)))
+            })
+            .collect()
     }
 
-    pub(crate) fn generate_new_population(&self) -> Vec<Arm> {
+    pub(crate) fn generate_new_population(&mut self) -> Result<Vec<Arm>, GaError> {
         let mut individuals: Vec<Arm> = Vec::new();
-        let mut rng = rand::thread_rng();
+        let mut collisions = 0;
+        let mut candidates = 0;
+        let mut proposals = self.initial_candidates().into_iter();
 
         while individuals.len() < self.population_size {
+            let candidate_arm = match proposals.next() {
+                Some(proposal) => proposal,
+                None => self.random_individual(),
+            };
+
+            candidates += 1;
+            if self.is_crowded(&candidate_arm, &individuals) {
+                collisions += 1;
+                if collisions >= self.max_resample_attempts {
+                    self.record_refill(collisions, candidates);
+                    return Err(GaError::ResampleExhausted {
+                        attempts: collisions,
+                    });
+                }
+            } else {
+                individuals.push(candidate_arm);
+            }
+        }
+
+        self.record_refill(collisions, candidates);
+        Ok(individuals)
+    }
+
+    // DE/rand/1 donor a + f * (b - c), rounded and clamped to the bounds
+    fn de_donor(&self, a: &Arm, b: &Arm, c: &Arm, f: f64) -> Arm {
+        let donor: Vec<i32> = (0..self.dimension)
+            .map(|j| {
+                let difference = b.get_action_vector()[j] as f64 - c.get_action_vector()[j] as f64;
+                let value = a.get_action_vector()[j] as f64 + f * difference;
+                self.snap_to_grid(j, value)
+            })
+            .collect();
+
+        self.apply_activity_mask(Arm::new(&self.pin_frozen_genes(donor)))
+    }
+
+    pub(crate) fn de_mutation(&mut self, target: &Arm, population: &[Arm], f: f64) -> Arm {
+        let candidates: Vec<&Arm> = population.iter().filter(|arm| *arm != target).collect();
+        assert!(
+            candidates.len() >= 3,
+            "differential mutation needs three individuals besides the target"
+        );
+
+        let chosen: Vec<&&Arm> = candidates.choose_multiple(&mut self.rng, 3).collect();
+        self.de_donor(chosen[0], chosen[1], chosen[2], f)
+    }
+
+    // DE exponential crossover: a run of genes starting at a random index (wrapping around
+    // the end) is copied from the donor, each further gene with probability
+    // `crossover_probability`
+    pub(crate) fn exponential_crossover(
+        &mut self,
+        target: &Arm,
+        donor: &Arm,
+        crossover_probability: f64,
+    ) -> Arm {
+        let mut genes = target.get_action_vector().to_vec();
+        let start = self.rng.gen_range(0..self.dimension);
+        let mut length = 0;
+        loop {
+            let j = (start + length) % self.dimension;
+            genes[j] = donor.get_action_vector()[j];
+            length += 1;
+            if length == self.dimension || self.rng.gen::<f64>() >= crossover_probability {
+                break;
+            }
+        }
+
+        self.apply_activity_mask(Arm::new(&self.pin_frozen_genes(genes)))
+    }
+
+    // uniform crossover biased towards the fitter (lower cost) parent: each gene comes from
+    // it with probability 0.5 + 0.5 * |cost_1 - cost_2| / (|cost_1| + |cost_2|)
+    pub(crate) fn fitness_weighted_crossover(
+        &mut self,
+        parent_1: &Arm,
+        cost_1: f64,
+        parent_2: &Arm,
+        cost_2: f64,
+    ) -> Arm {
+        let (fitter, other) = if cost_1 <= cost_2 {
+            (parent_1, parent_2)
+        } else {
+            (parent_2, parent_1)
+        };
+        let magnitude = cost_1.abs() + cost_2.abs();
+        let bias = if magnitude > 0.0 {
+            0.5 + 0.5 * (cost_1 - cost_2).abs() / magnitude
+        } else {
+            0.5
+        };
+
+        let genes: Vec<i32> = (0..self.dimension)
+            .map(|j| {
+                if self.rng.gen::<f64>() < bias {
+                    fitter.get_action_vector()[j]
+                } else {
+                    other.get_action_vector()[j]
+                }
+            })
+            .collect();
+
+        self.apply_activity_mask(Arm::new(&self.pin_frozen_genes(genes)))
+    }
+
+    // the individuals one grid step away from `center` in a single active, unfrozen gene
+    pub(crate) fn neighbours(&self, center: &Arm) -> Vec<Arm> {
+        let mut neighbours = Vec::new();
+        for j in 0..self.dimension {
+            if !center.is_gene_active(j) || self.frozen_genes[j].is_some() {
+                continue;
+            }
+            for direction in [-1, 1] {
+                let mut genes = center.get_action_vector().to_vec();
+                let value = i64::from(genes[j]) + direction * i64::from(self.steps[j]);
+                if value < i64::from(self.lower_bound[j]) || value > i64::from(self.upper_bound[j])
+                {
+                    continue;
+                }
+                genes[j] = value as i32;
+                neighbours.push(self.apply_activity_mask(Arm::new(&genes)));
+            }
+        }
+        neighbours
+    }
+
+    // unique individuals whose genes deviate from `center` by at most `spread` times the
+    // range of the gene
+    pub(crate) fn generate_around(&mut self, center: &Arm, spread: f64, count: usize) -> Vec<Arm> {
+        let mut individuals: Vec<Arm> = Vec::new();
+        let mut collisions = 0;
+
+        while individuals.len() < count && collisions < self.max_resample_attempts {
             let candidate_solution: Vec<i32> = (0..self.dimension)
-                .map(|j| rng.gen_range(self.lower_bound[j]..=self.upper_bound[j]))
+                .map(|j| {
+                    let radius = spread * self.range(j) as f64;
+                    let offset = if radius > 0.0 {
+                        self.rng.gen_range(-radius..=radius)
+                    } else {
+                        0.0
+                    };
+                    self.snap_to_grid(j, center.get_action_vector()[j] as f64 + offset)
+                })
                 .collect();
 
-            let candidate_arm = Arm::new(&candidate_solution);
+            let candidate_solution = self.pin_frozen_genes(candidate_solution);
+            let candidate_arm = self.apply_activity_mask(Arm::new(&candidate_solution));
 
-            if !individuals.contains(&candidate_arm) {
+            if candidate_arm == *center || individuals.contains(&candidate_arm) {
+                collisions += 1;
+            } else {
                 individuals.push(candidate_arm);
             }
         }
+
         individuals
     }
 
-    pub(crate) fn crossover(&self, population: &[Arm]) -> Vec<Arm> {
+    #[cfg(test)]
+    pub(crate) fn crossover(&mut self, population: &[Arm]) -> Vec<Arm> {
+        self.crossover_with_flags(population).0
+    }
+
+    // crossed population together with whether each child was produced by crossing the
+    // genes of its parents rather than passed through
+    pub(crate) fn crossover_with_flags(&mut self, population: &[Arm]) -> (Vec<Arm>, Vec<bool>) {
+        // disabled crossover passes the population through in its original order
+        if self.crossover_rate == 0.0 {
+            return (population.to_vec(), vec![false; population.len()]);
+        }
+
         let mut crossover_pop: Vec<Arm> = Vec::new();
-        let population_size = self.population_size;
-        let mut rng = rand::thread_rng();
+        let mut crossed = Vec::new();
+        let population_size = population.len();
 
-        for i in (0..population_size).step_by(2) {
-            if rand::random::<f64>() < self.crossover_rate {
+        for (i, j) in self.mating_pairs(population_size) {
+            if self.rng.gen::<f64>() < self.crossover_rate {
                 // Crossover
                 let max_dim_index = self.dimension - 1;
-                let swap_rv = rng.gen_range(1..=max_dim_index);
-
-                for j in 1..=max_dim_index {
-                    if swap_rv == j {
-                        let mut cross_vec_1: Vec<i32> =
-                            population[i].get_action_vector()[0..j].to_vec();
-                        cross_vec_1.extend_from_slice(
-                            &population[i + 1].get_action_vector()[j..=max_dim_index],
-                        );
-
-                        let mut cross_vec_2: Vec<i32> =
-                            population[i + 1].get_action_vector()[0..j].to_vec();
-                        cross_vec_2.extend_from_slice(
-                            &population[i].get_action_vector()[j..=max_dim_index],
-                        );
-
-                        let new_individual_1 = Arm::new(&cross_vec_1);
-                        let new_individual_2 = Arm::new(&cross_vec_2);
-
-                        crossover_pop.push(new_individual_1);
-                        crossover_pop.push(new_individual_2);
-                    }
-                }
+                let swap_rv = self.rng.gen_range(1..=max_dim_index);
+
+                let parent_1 = self.apply_activity_mask(population[i].clone());
+                let parent_2 = self.apply_activity_mask(population[j].clone());
+
+                crossover_pop.push(self.cross_genes(&parent_1, &parent_2, swap_rv));
+                crossover_pop.push(self.cross_genes(&parent_2, &parent_1, swap_rv));
+                crossed.extend([true, true]);
             } else {
                 // No Crossover
                 crossover_pop.push(population[i].clone());
-                crossover_pop.push(population[i + 1].clone());
+                crossover_pop.push(population[j].clone());
+                crossed.extend([false, false]);
             }
         }
 
-        crossover_pop
+        if population_size % 2 == 1 {
+            let leftover = self.apply_activity_mask(population[population_size - 1].clone());
+            let (child, by_crossover) = match self.odd_crossover {
+                OddCrossover::AverageWithBest if self.rng.gen::<f64>() < self.crossover_rate => {
+                    let best = self.apply_activity_mask(population[0].clone());
+                    (self.average_genes(&leftover, &best), true)
+                }
+                _ => (leftover, false),
+            };
+            crossover_pop.push(child);
+            crossed.push(by_crossover);
+        }
+
+        if self.crossover_dedup {
+            return self.dedup_offspring(crossover_pop, crossed);
+        }
+        (crossover_pop, crossed)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn mutate(&mut self, population: &[Arm]) -> Vec<Arm> {
+        self.mutate_with_sources(population).0
     }
 
-    pub(crate) fn mutate(&self, population: &[Arm]) -> Vec<Arm> {
+    // mutated population together with the index into `population` each individual was
+    // mutated from
+    pub(crate) fn mutate_with_sources(&mut self, population: &[Arm]) -> (Vec<Arm>, Vec<usize>) {
         let mut mutated_population = Vec::new();
-        let mut seen = HashSet::new();
-        let mut rng = rand::thread_rng();
+        let mut sources = Vec::new();
+        let mut seen = Set::new();
+
+        for (source, individual) in population.iter().enumerate() {
+            let individual = self.apply_activity_mask(individual.clone());
 
-        for individual in population.iter() {
             // Clone the action vector
             let mut new_action_vector = individual.get_action_vector().to_vec(); // Here I assumed `get_action_vector` returns a slice or Vec
 
             for (i, value) in new_action_vector.iter_mut().enumerate() {
-                if rng.gen::<f64>() < self.mutation_rate {
-                    let adjustment = Normal::new(
-                        0.0,
-                        self.mutation_span * (self.upper_bound[i] - self.lower_bound[i]) as f64,
-                    )
-                    .unwrap()
-                    .sample(&mut rng);
-
-                    *value = (*value as f64 + adjustment)
-                        .max(self.lower_bound[i] as f64)
-                        .min(self.upper_bound[i] as f64) as i32;
+                if !individual.is_gene_active(i) || self.frozen_genes[i].is_some() {
+                    continue;
+                }
+
+                if self.rng.gen::<f64>() < self.mutation_rate {
+                    let adjustment = self.sample_adjustment(self.mutation_sigma(i));
+
+                    *value = self.snap_to_grid(i, *value as f64 + adjustment);
                 }
             }
 
-            let new_individual = Arm::new(new_action_vector.as_slice());
+            let new_action_vector = self.pin_frozen_genes(new_action_vector);
+            let new_individual = self.apply_activity_mask(Arm::new(new_action_vector.as_slice()));
 
-            if seen.insert(new_individual.clone()) {
+            let is_new = match self.min_distance {
+                Some(_) => !self.is_crowded(&new_individual, &mutated_population),
+                None => {
+                    self.allow_duplicates
+                        || seen.insert(new_individual.get_action_vector().to_vec())
+                }
+            };
+            if is_new {
                 mutated_population.push(new_individual);
+                sources.push(source);
             }
         }
 
-        mutated_population
+        let candidates = population.len();
+        self.record_refill(candidates - mutated_population.len(), candidates);
+        (mutated_population, sources)
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_range_loop, clippy::useless_vec)]
 mod tests {
     use super::*;
 
@@ -160,7 +928,7 @@ mod tests {
 
     #[test]
     fn test_mutate() {
-        let ga = GeneticAlgorithm::new(
+        let mut ga = GeneticAlgorithm::new(
             mock_opti_function,
             2,   // Two individuals in population
             1.0, // 100% mutation rate for demonstration
@@ -191,7 +959,7 @@ mod tests {
 
     #[test]
     fn test_crossover() {
-        let ga = GeneticAlgorithm::new(
+        let mut ga = GeneticAlgorithm::new(
             mock_opti_function,
             2, // Two individuals for simplicity
             0.1,
@@ -219,4 +987,723 @@ mod tests {
             initial_population[1].get_action_vector()
         );
     }
+
+    #[test]
+    fn test_mutate_near_integer_limits() {
+        let lower_bound = vec![i32::MAX - 10, i32::MIN, i32::MIN];
+        let upper_bound = vec![i32::MAX, i32::MIN + 10, i32::MAX];
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            4,
+            1.0,
+            0.9,
+            1.0,
+            3,
+            lower_bound.clone(),
+            upper_bound.clone(),
+        );
+
+        let mut population = vec![
+            Arm::new(&[i32::MAX, i32::MIN, i32::MAX]),
+            Arm::new(&[i32::MAX - 10, i32::MIN + 10, i32::MIN]),
+        ];
+        for _ in 0..200 {
+            population = ga.mutate(&population);
+            for individual in &population {
+                for (j, value) in individual.get_action_vector().iter().enumerate() {
+                    assert!(*value >= lower_bound[j] && *value <= upper_bound[j]);
+                }
+                // values near i32::MAX must not wrap around to negative ones
+                assert!(individual.get_action_vector()[0] > 0);
+            }
+        }
+
+        let population = ga.generate_new_population().unwrap();
+        for individual in &population {
+            assert!(individual.get_action_vector()[0] > 0);
+            assert!(individual.get_action_vector()[1] < 0);
+        }
+        let neighbours = ga.neighbours(&Arm::new(&[i32::MAX, i32::MIN, 0]));
+        assert_eq!(neighbours.len(), 4);
+    }
+
+    #[test]
+    fn test_mutate_skips_inactive_genes() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            2,
+            1.0, // 100% mutation rate so every active gene is perturbed
+            0.9,
+            1.0,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        // the second gene only matters while the flag gene is set to 1
+        ga.set_activity_mask(Box::new(|x: &[i32]| vec![true, x[0] == 1]));
+
+        let initial_population = vec![Arm::new(&[0, 5]), Arm::new(&[2, 7])];
+
+        for _ in 0..20 {
+            let mutated_population = ga.mutate(&initial_population);
+
+            // the inactive gene is set to its lower bound and not mutated
+            for individual in &mutated_population {
+                assert_eq!(individual.get_action_vector()[1], 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_crossover_keeps_inactive_genes() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            2,
+            0.1,
+            1.0, // 100% crossover rate
+            0.5,
+            3,
+            vec![0, 0, 0],
+            vec![10, 10, 10],
+        );
+        // the last gene is inactive whenever the flag gene is 0
+        ga.set_activity_mask(Box::new(|x: &[i32]| vec![true, true, x[0] == 1]));
+
+        let initial_population = vec![Arm::new(&[1, 2, 3]), Arm::new(&[0, 8, 9])];
+
+        for _ in 0..20 {
+            let crossover_population = ga.crossover(&initial_population);
+
+            for (child, kept) in crossover_population.iter().zip([3, 9]) {
+                let expected = match child.get_action_vector()[0] {
+                    1 => kept,
+                    _ => 0,
+                };
+                assert_eq!(child.get_action_vector()[2], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_diversity_uses_distance_metric() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            3,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+
+        let population = vec![Arm::new(&[0, 0]), Arm::new(&[3, 4]), Arm::new(&[0, 4])];
+
+        // euclidean by default: (5 + 4 + 3) / 3
+        assert_eq!(ga.diversity(&population), 4.0);
+
+        ga.set_distance_metric(Box::new(|a: &[i32], b: &[i32]| {
+            a.iter().zip(b.iter()).filter(|(x, y)| x != y).count() as f64
+        }));
+
+        // hamming: (2 + 1 + 1) / 3
+        assert_eq!(ga.diversity(&population), 4.0 / 3.0);
+        assert_eq!(ga.diversity(&population[..1]), 0.0);
+    }
+
+    #[test]
+    fn test_cauchy_mutation_jumps_further_than_gaussian() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            1,
+            1.0,
+            0.9,
+            0.01, // nominal scale of 10 for a range of 1000
+            1,
+            vec![0],
+            vec![1000],
+        );
+        let population = vec![Arm::new(&[500])];
+
+        fn count_large_jumps<F: OptimizationFn>(
+            ga: &mut GeneticAlgorithm<F>,
+            population: &[Arm],
+        ) -> i32 {
+            let mut large_jumps = 0;
+            for _ in 0..1000 {
+                let value = ga.mutate(population)[0].get_action_vector()[0];
+                assert!((0..=1000).contains(&value));
+                if (value - 500).abs() > 50 {
+                    large_jumps += 1;
+                }
+            }
+            large_jumps
+        }
+
+        let gaussian_jumps = count_large_jumps(&mut ga, &population);
+        ga.set_mutation_distribution(MutationDistribution::Cauchy);
+        let cauchy_jumps = count_large_jumps(&mut ga, &population);
+
+        assert!(cauchy_jumps > 50);
+        assert!(cauchy_jumps > gaussian_jumps);
+    }
+
+    #[test]
+    fn test_mating_pairs() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            10,
+            0.1,
+            1.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        assert_eq!(
+            ga.mating_pairs(10),
+            vec![(0, 1), (2, 3), (4, 5), (6, 7), (8, 9)]
+        );
+
+        ga.set_pairing(Pairing::RandomShuffle);
+        ga.set_seed(1);
+        let pairs_seed_1 = ga.mating_pairs(10);
+        ga.set_seed(2);
+        let pairs_seed_2 = ga.mating_pairs(10);
+        assert_ne!(pairs_seed_1, pairs_seed_2);
+
+        // every individual is still mated exactly once
+        let mut parents: Vec<usize> = pairs_seed_1.iter().flat_map(|&(i, j)| [i, j]).collect();
+        parents.sort();
+        assert_eq!(parents, (0..10).collect::<Vec<usize>>());
+
+        ga.set_pairing(Pairing::BestWithRandom);
+        for (k, (i, j)) in ga.mating_pairs(10).into_iter().enumerate() {
+            assert_eq!(i, k);
+            assert_ne!(i, j);
+            assert!(j < 10);
+        }
+    }
+
+    #[test]
+    fn test_select_mating_pool() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            4,
+            0.1,
+            1.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        let population = vec![
+            Arm::new(&[1, 1]),
+            Arm::new(&[2, 2]),
+            Arm::new(&[3, 3]),
+            Arm::new(&[4, 4]),
+        ];
+        let fitnesses = [1.0, 2.0, 3.0, 4.0];
+
+        assert_eq!(ga.select_mating_pool(&population, &fitnesses), population);
+
+        // without scaling the worst individual has no chance of being selected
+        ga.set_parent_selection(ParentSelection::Roulette, None);
+        let mating_pool = ga.select_mating_pool(&population, &fitnesses);
+        assert_eq!(mating_pool.len(), 4);
+        assert!(!mating_pool.contains(&population[3]));
+
+        // a tournament of the whole population without replacement always picks the best
+        ga.set_parent_selection(ParentSelection::Tournament, None);
+        ga.set_tournament(4, false);
+        let mating_pool = ga.select_mating_pool(&population, &fitnesses);
+        assert_eq!(mating_pool, vec![population[0].clone(); 4]);
+    }
+
+    #[test]
+    fn test_mutate_with_custom_perturbation() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            2,
+            1.0,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        ga.set_perturbation(Box::new(|_scale, _rng| 2.0));
+
+        let population = vec![Arm::new(&[1, 5]), Arm::new(&[3, 9])];
+        let mutated_population = ga.mutate(&population);
+
+        assert_eq!(mutated_population[0].get_action_vector(), &[3, 7]);
+        // genes are still clamped to the bounds
+        assert_eq!(mutated_population[1].get_action_vector(), &[5, 10]);
+    }
+
+    #[test]
+    fn test_saturation_expands_mutation_span() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            4,
+            0.0, // no mutation, every copy of an individual collides
+            0.9,
+            0.1,
+            2,
+            vec![0, 0],
+            vec![1, 1],
+        );
+        ga.set_saturation_threshold(Some(0.4));
+
+        let population = vec![Arm::new(&[0, 1]), Arm::new(&[1, 0])];
+        ga.mutate(&population);
+        assert_eq!(ga.collision_rate(), 0.0);
+        assert_eq!(ga.get_saturation_responses(), 0);
+
+        let saturated_population = vec![
+            Arm::new(&[0, 1]),
+            Arm::new(&[0, 1]),
+            Arm::new(&[0, 1]),
+            Arm::new(&[1, 0]),
+        ];
+        ga.mutate(&saturated_population);
+        assert_eq!(ga.collision_rate(), 0.5);
+        assert_eq!(ga.get_saturation_responses(), 1);
+        assert_eq!(ga.get_mutation_span(), 0.2);
+        ga.mutate(&saturated_population);
+        assert_eq!(ga.get_mutation_span(), 0.4);
+
+        // the original span returns once the collision rate drops below the threshold
+        ga.mutate(&population);
+        assert_eq!(ga.collision_rate(), 0.0);
+        assert_eq!(ga.get_saturation_responses(), 2);
+        assert_eq!(ga.get_mutation_span(), 0.1);
+    }
+
+    #[test]
+    fn test_generate_new_population_resample_exhausted() {
+        // only 4 distinct individuals exist in the search space
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            5,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![1, 1],
+        );
+        ga.set_max_resample_attempts(100);
+
+        assert_eq!(
+            ga.generate_new_population(),
+            Err(GaError::ResampleExhausted { attempts: 100 })
+        );
+
+        ga.population_size = 4;
+        assert_eq!(ga.generate_new_population().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_de_mutation() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            4,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![20, 20],
+        );
+        let a = Arm::new(&[10, 10]);
+        let b = Arm::new(&[6, 2]);
+        let c = Arm::new(&[3, 7]);
+
+        // 10 + 0.5 * 3 = 11.5 and 10 + 0.5 * -5 = 7.5
+        assert_eq!(ga.de_donor(&a, &b, &c, 0.5).get_action_vector(), &[12, 8]);
+        // clamped to the bounds
+        assert_eq!(ga.de_donor(&a, &b, &c, 5.0).get_action_vector(), &[20, 0]);
+
+        let target = Arm::new(&[0, 0]);
+        let population = vec![target.clone(), a.clone(), b.clone(), c.clone()];
+        let donor = ga.de_mutation(&target, &population, 1.0);
+
+        let possible_donors: Vec<Arm> = [
+            [&a, &b, &c],
+            [&a, &c, &b],
+            [&b, &a, &c],
+            [&b, &c, &a],
+            [&c, &a, &b],
+            [&c, &b, &a],
+        ]
+        .iter()
+        .map(|[x, y, z]| ga.de_donor(x, y, z, 1.0))
+        .collect();
+        assert!(possible_donors.contains(&donor));
+    }
+
+    #[test]
+    fn test_search_space_size_with_steps_and_frozen_genes() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            10,
+            1.0,
+            1.0,
+            0.5,
+            3,
+            vec![3, -10, 0],
+            vec![50, 10, 9],
+        );
+        assert_eq!(ga.search_space_size(), 48 * 21 * 10);
+
+        // 3, 8, .., 48 and -10, -6, .., 10
+        ga.set_steps(vec![5, 4, 1]);
+        assert_eq!(ga.search_space_size(), 10 * 6 * 10);
+
+        ga.freeze_gene(2, 4);
+        assert_eq!(ga.search_space_size(), 10 * 6);
+        ga.unfreeze_gene(2);
+        assert_eq!(ga.search_space_size(), 10 * 6 * 10);
+
+        let wide = GeneticAlgorithm::new(
+            mock_opti_function,
+            10,
+            1.0,
+            1.0,
+            0.5,
+            3,
+            vec![i32::MIN; 3],
+            vec![i32::MAX; 3],
+        );
+        assert_eq!(wide.search_space_size(), usize::MAX);
+    }
+
+    #[test]
+    fn test_steps_keep_genes_on_grid() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            10,
+            1.0,
+            1.0,
+            0.5,
+            2,
+            vec![3, -10],
+            vec![50, 10],
+        );
+        ga.set_steps(vec![5, 4]);
+        ga.set_seed(13);
+
+        assert_eq!(ga.snap_to_grid(0, 11.0), 13);
+        assert_eq!(ga.snap_to_grid(0, 10.4), 8);
+        assert_eq!(ga.snap_to_grid(0, 100.0), 48);
+        assert_eq!(ga.snap_to_grid(1, -13.0), -10);
+
+        let on_grid = |population: &[Arm]| {
+            population.iter().all(|arm| {
+                let genes = arm.get_action_vector();
+                (genes[0] - 3) % 5 == 0 && (genes[1] + 10) % 4 == 0
+            })
+        };
+
+        let population = ga.generate_new_population().unwrap();
+        assert!(on_grid(&population));
+        for _ in 0..10 {
+            let crossover_pop = ga.crossover(&population);
+            assert!(on_grid(&crossover_pop));
+            assert!(on_grid(&ga.mutate(&crossover_pop)));
+        }
+    }
+
+    #[test]
+    fn test_crossover_odd_population() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            3,
+            0.1,
+            1.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        assert_eq!(ga.mating_pairs(3), vec![(0, 1)]);
+
+        let population = vec![Arm::new(&[1, 2]), Arm::new(&[3, 4]), Arm::new(&[5, 6])];
+        for pairing in [
+            Pairing::Adjacent,
+            Pairing::RandomShuffle,
+            Pairing::BestWithRandom,
+        ] {
+            ga.set_pairing(pairing);
+            let crossover_population = ga.crossover(&population);
+            assert_eq!(crossover_population.len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_min_distance_spaces_individuals() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            10,
+            1.0,
+            1.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![20, 20],
+        );
+        ga.set_seed(7);
+        ga.set_min_distance(Some(3.0));
+
+        let population = ga.generate_new_population().unwrap();
+        assert_eq!(population.len(), 10);
+        let mutated_population = ga.mutate(&population);
+
+        for individuals in [&population, &mutated_population] {
+            for (i, a) in individuals.iter().enumerate() {
+                for b in &individuals[i + 1..] {
+                    assert!(ga.distance(a, b) >= 3.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_allow_duplicates_keeps_population_size() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            6,
+            0.0, // no mutation
+            0.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        let population = vec![
+            Arm::new(&[1, 1]),
+            Arm::new(&[1, 1]),
+            Arm::new(&[2, 2]),
+            Arm::new(&[2, 2]),
+            Arm::new(&[2, 2]),
+            Arm::new(&[3, 3]),
+        ];
+        assert_eq!(ga.mutate(&population).len(), 3);
+
+        ga.set_allow_duplicates(true);
+        assert_eq!(ga.mutate(&population), population);
+
+        // a two point search space still fills a population of six
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            6,
+            0.0,
+            0.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![1, 0],
+        );
+        ga.set_allow_duplicates(true);
+        assert_eq!(ga.generate_new_population().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_crossover_disabled_clones_population() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            5,
+            0.1,
+            0.0, // no crossover
+            0.5,
+            1,
+            vec![0],
+            vec![10],
+        );
+        ga.set_pairing(Pairing::RandomShuffle);
+        let population: Vec<Arm> = (0..5).map(|i| Arm::new(&[i])).collect();
+
+        let crossover_population = ga.crossover(&population);
+        assert_eq!(crossover_population, population);
+    }
+
+    #[test]
+    fn test_odd_crossover_averages_leftover_with_best() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            5,
+            0.1,
+            1.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        ga.set_odd_crossover(OddCrossover::AverageWithBest);
+        let population = vec![
+            Arm::new(&[0, 10]),
+            Arm::new(&[1, 1]),
+            Arm::new(&[2, 2]),
+            Arm::new(&[3, 3]),
+            Arm::new(&[4, 7]),
+        ];
+
+        let crossover_population = ga.crossover(&population);
+        assert_eq!(crossover_population.len(), 5);
+        assert_eq!(crossover_population[4].get_action_vector(), &[2, 9]);
+    }
+
+    #[test]
+    fn test_crossover_dedup_refills_offspring() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            4,
+            0.1,
+            1.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        let population = vec![Arm::new(&[3, 3]); 4];
+
+        // identical parents only produce copies of themselves
+        let crossover_population = ga.crossover(&population);
+        assert_eq!(crossover_population, population);
+
+        ga.set_crossover_dedup(true);
+        let crossover_population = ga.crossover(&population);
+        assert_eq!(crossover_population.len(), 4);
+        assert_eq!(crossover_population[0], population[0]);
+        let unique: Set<Vec<i32>> = crossover_population
+            .iter()
+            .map(|arm| arm.get_action_vector().to_vec())
+            .collect();
+        assert_eq!(unique.len(), 4);
+    }
+
+    #[test]
+    fn test_crossover_flags_crossed_children() {
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            5,
+            0.1,
+            1.0,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        let population = vec![
+            Arm::new(&[0, 10]),
+            Arm::new(&[1, 1]),
+            Arm::new(&[2, 2]),
+            Arm::new(&[3, 3]),
+            Arm::new(&[4, 7]),
+        ];
+
+        // the unpaired leftover is passed through
+        let (_, crossed) = ga.crossover_with_flags(&population);
+        assert_eq!(crossed, vec![true, true, true, true, false]);
+
+        ga.set_odd_crossover(OddCrossover::AverageWithBest);
+        let (_, crossed) = ga.crossover_with_flags(&population);
+        assert_eq!(crossed, vec![true; 5]);
+
+        // duplicate children are refilled with random individuals
+        ga.set_crossover_dedup(true);
+        let (offspring, crossed) = ga.crossover_with_flags(&vec![Arm::new(&[3, 3]); 4]);
+        assert_eq!(offspring.len(), crossed.len());
+        assert_eq!(
+            crossed.iter().filter(|&&by_crossover| by_crossover).count(),
+            1
+        );
+
+        ga.crossover_rate = 0.0;
+        let (_, crossed) = ga.crossover_with_flags(&population);
+        assert_eq!(crossed, vec![false; 5]);
+    }
+
+    #[test]
+    fn test_exponential_crossover() {
+        let dimension = 10;
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            4,
+            0.1,
+            0.9,
+            0.5,
+            dimension,
+            vec![0; dimension],
+            vec![1; dimension],
+        );
+        let target = Arm::new(&[0; 10]);
+        let donor = Arm::new(&[1; 10]);
+
+        let trials = 20000;
+        let mut total_length = 0;
+        for _ in 0..trials {
+            let trial = ga.exponential_crossover(&target, &donor, 0.5);
+            let copied: Vec<bool> = trial.get_action_vector().iter().map(|&v| v == 1).collect();
+            let length = copied.iter().filter(|&&c| c).count();
+            assert!(length >= 1);
+            // a contiguous run (modulo wraparound) has exactly one start
+            let starts = (0..dimension)
+                .filter(|&j| copied[j] && !copied[(j + dimension - 1) % dimension])
+                .count();
+            assert!(starts == 1 || length == dimension);
+            total_length += length;
+        }
+
+        // truncated geometric length: (1 - p^d) / (1 - p)
+        let expected = (1.0 - 0.5f64.powi(dimension as i32)) / 0.5;
+        let mean = total_length as f64 / trials as f64;
+        assert!((mean - expected).abs() < 0.05);
+
+        // a probability of one copies the whole donor
+        assert_eq!(ga.exponential_crossover(&target, &donor, 1.0), donor);
+    }
+
+    #[test]
+    fn test_fitness_weighted_crossover_favours_fitter_parent() {
+        let dimension = 10;
+        let mut ga = GeneticAlgorithm::new(
+            mock_opti_function,
+            4,
+            0.1,
+            0.9,
+            0.5,
+            dimension,
+            vec![0; dimension],
+            vec![1; dimension],
+        );
+        let fitter = Arm::new(&[0; 10]);
+        let weaker = Arm::new(&[1; 10]);
+
+        let trials = 2000;
+        let share_from_fitter = |ga: &mut GeneticAlgorithm<_>, cost_1, cost_2| {
+            let inherited: usize = (0..trials)
+                .map(|_| {
+                    let child = ga.fitness_weighted_crossover(&weaker, cost_2, &fitter, cost_1);
+                    child
+                        .get_action_vector()
+                        .iter()
+                        .filter(|&&v| v == 0)
+                        .count()
+                })
+                .sum();
+            inherited as f64 / (trials * dimension) as f64
+        };
+
+        // a much fitter parent passes on 0.5 + 0.5 * 9 / 11 of its genes
+        let share = share_from_fitter(&mut ga, 1.0, 10.0);
+        assert!(share > 0.5);
+        assert!((share - (0.5 + 0.5 * 9.0 / 11.0)).abs() < 0.02);
+
+        // equally fit parents are crossed uniformly
+        let share = share_from_fitter(&mut ga, 3.0, 3.0);
+        assert!((share - 0.5).abs() < 0.02);
+    }
 }
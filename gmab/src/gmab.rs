@@ -1,50 +1,397 @@
-use crate::arm::{Arm, OptimizationFn};
+use crate::arm::{normal_cdf, normal_quantile, Arm, OptimizationFn};
+use crate::config::{
+    GmabConfig, Objective, ObjectiveOffset, ParentSelection, ReplacementMode, RestartMode, TieBreak,
+};
+use crate::error::GaError;
 use crate::genetic::GeneticAlgorithm;
-use rand::prelude::SliceRandom;
-use std::collections::HashMap;
+use crate::selection::rank_order;
+use crate::Map;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::cmp::Ordering;
+use core::fmt;
+use core::panic::RefUnwindSafe;
+use core::time::Duration;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+use rand::{Rng, RngCore};
+#[cfg(feature = "std")]
+use std::panic::catch_unwind;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 use crate::sorted_multi_map::{FloatKey, SortedMultiMap};
+use crate::stopping::{RunState, StoppingCriterion};
+use crate::surrogate::Surrogate;
+use crate::trace::RunTrace;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicationSummary {
+    pub replications: usize,
+    pub mean_best_fitness: f64,
+    pub std_best_fitness: f64,
+    pub min_best_fitness: f64,
+    pub max_best_fitness: f64,
+    // share of replications reaching the configured target fitness
+    pub success_rate: Option<f64>,
+}
+
+impl ReplicationSummary {
+    fn from_best_fitnesses(
+        best_fitnesses: &[f64],
+        target_fitness: Option<f64>,
+        objective: Objective,
+    ) -> Self {
+        let replications = best_fitnesses.len();
+        let mean_best_fitness = best_fitnesses.iter().sum::<f64>() / replications as f64;
+        let std_best_fitness = if replications > 1 {
+            (best_fitnesses
+                .iter()
+                .map(|fitness| (fitness - mean_best_fitness).powi(2))
+                .sum::<f64>()
+                / (replications - 1) as f64)
+                .sqrt()
+        } else {
+            0.0
+        };
+        let success_rate = target_fitness.map(|target| {
+            best_fitnesses
+                .iter()
+                .filter(|&&fitness| objective.cost(fitness) <= objective.cost(target))
+                .count() as f64
+                / replications as f64
+        });
+
+        ReplicationSummary {
+            replications,
+            mean_best_fitness,
+            std_best_fitness,
+            min_best_fitness: best_fitnesses.iter().copied().fold(f64::MAX, f64::min),
+            max_best_fitness: best_fitnesses.iter().copied().fold(f64::MIN, f64::max),
+            success_rate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonResult {
+    pub wins_a: usize,
+    pub wins_b: usize,
+    pub ties: usize,
+    pub mean_difference: f64,
+    // paired t statistic of the differences, zero when they do not vary
+    pub t_statistic: f64,
+}
+
+impl ComparisonResult {
+    fn from_differences(differences: &[f64]) -> Self {
+        let n = differences.len() as f64;
+        let mean_difference = differences.iter().sum::<f64>() / n;
+        let std_difference = if differences.len() > 1 {
+            (differences
+                .iter()
+                .map(|difference| (difference - mean_difference).powi(2))
+                .sum::<f64>()
+                / (n - 1.0))
+                .sqrt()
+        } else {
+            0.0
+        };
+        let t_statistic = if std_difference > 0.0 {
+            mean_difference / (std_difference / n.sqrt())
+        } else {
+            0.0
+        };
+
+        ComparisonResult {
+            wins_a: differences.iter().filter(|&&d| d < 0.0).count(),
+            wins_b: differences.iter().filter(|&&d| d > 0.0).count(),
+            ties: differences.iter().filter(|&&d| d == 0.0).count(),
+            mean_difference,
+            t_statistic,
+        }
+    }
+}
+
+// seed of the `stream`-th island or thread, derived from the master seed with a
+// SplitMix64 step so that neighbouring streams get unrelated seeds
+pub fn derive_seed(master_seed: u64, stream: u64) -> u64 {
+    let mut z =
+        master_seed.wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[derive(Debug, Clone)]
+pub struct RunSummary {
+    pub best_action_vector: Vec<i32>,
+    pub best_fitness: f64,
+    pub simulations_used: usize,
+    pub generations: usize,
+    pub diversity: f64,
+    pub wall_time: Duration,
+}
+
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "best arm:         {:?}", self.best_action_vector)?;
+        writeln!(f, "best fitness:     {:.6}", self.best_fitness)?;
+        writeln!(f, "simulations used: {}", self.simulations_used)?;
+        writeln!(f, "generations:      {}", self.generations)?;
+        writeln!(f, "diversity:        {:.3}", self.diversity)?;
+        write!(f, "wall time:        {:.3?}", self.wall_time)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EvaluationTiming {
+    pub evaluations: usize,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl EvaluationTiming {
+    #[cfg(feature = "std")]
+    fn record(&mut self, duration: Duration) {
+        self.evaluations += 1;
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
+
+    // a batch counts as `evaluations` evaluations of equal duration
+    #[cfg(feature = "std")]
+    fn record_batch(&mut self, duration: Duration, evaluations: usize) {
+        if evaluations == 0 {
+            return;
+        }
+        let share = duration / evaluations as u32;
+        self.evaluations += evaluations;
+        self.total += duration;
+        self.max = self.max.max(share);
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.evaluations == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.evaluations as u32
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationRecord {
+    pub action_vector: Vec<i32>,
+    pub fitness: f64,
+    // generations completed before the evaluation, 0 for the initial population
+    pub generation: usize,
+    // time since construction, always zero without std
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CachedFitness {
+    fitness: f64,
+    #[cfg(feature = "std")]
+    stored: Instant,
+}
+
+impl CachedFitness {
+    fn new(fitness: f64) -> Self {
+        CachedFitness {
+            fitness,
+            #[cfg(feature = "std")]
+            stored: Instant::now(),
+        }
+    }
+
+    fn is_fresh(&self, ttl: Option<Duration>) -> bool {
+        #[cfg(feature = "std")]
+        if let Some(ttl) = ttl {
+            return self.stored.elapsed() <= ttl;
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = ttl;
+        true
+    }
+}
+
+type EvaluationCallback = Box<dyn FnMut(&[i32], f64) + Send + Sync>;
+
+// JSON lines written as the evaluations happen, the first write error ends the stream
+#[cfg(feature = "std")]
+struct HistoryStream {
+    writer: Box<dyn std::io::Write + Send + Sync>,
+    error: Option<std::io::Error>,
+}
 
 pub struct Gmab<F: OptimizationFn> {
     sample_average_tree: SortedMultiMap<FloatKey, i32>,
     arm_memory: Vec<Arm>,
-    lookup_table: HashMap<Vec<i32>, i32>,
+    lookup_table: Map<Vec<i32>, i32>,
     genetic_algorithm: GeneticAlgorithm<F>,
+    config: GmabConfig,
+    simulations_used: usize,
+    fitness_cache: Map<Vec<i32>, CachedFitness>,
+    // created by initialize and not yet evaluated
+    initial_population: Option<Vec<Arm>>,
+    population_evaluated: bool,
+    best_fitness_history: Vec<f64>,
+    stagnant_generations: usize,
+    generations_elapsed: usize,
+    // simulations used before the current generation started
+    generation_start: usize,
+    last_population_fitness: Vec<f64>,
+    best_arm_trajectory: Vec<Arm>,
+    // time spent running generations
+    wall_time: Duration,
+    // configured population size, if the population was shrunk to the search space
+    shrunk_population_from: Option<usize>,
+    // action vectors the objective function panicked on with catch_panics, once per panic
+    panicked_inputs: Vec<Vec<i32>>,
+    on_evaluate: Option<EvaluationCallback>,
+    // rewards of a batch evaluation not yet consumed by evaluate
+    prefetched: Map<Vec<i32>, Vec<f64>>,
+    // rewards of all evaluations in order, only recorded with record_trace
+    trace_rewards: Vec<f64>,
+    // only recorded with record_history
+    history: Vec<EvaluationRecord>,
+    #[cfg(feature = "std")]
+    history_stream: Option<HistoryStream>,
+    #[cfg(feature = "std")]
+    created: Instant,
+    surrogate: Option<Box<dyn Surrogate + Send + Sync>>,
+    // offspring per generation that pass the surrogate pre-screening
+    screened_offspring: usize,
+    evaluation_timing: EvaluationTiming,
+    worst_observed_cost: f64,
+    // arms forced into the population of the next generation, e.g. worse offspring admitted
+    // by simulated annealing or the individuals of a restart
+    injected_indexes: Vec<i32>,
+    protected_indexes: Vec<i32>,
+    // new best arms produced by crossover and by mutation, an offspring changed by both
+    // operators counts for both
+    crossover_improvements: u32,
+    mutation_improvements: u32,
+}
+
+// evaluates the objective function, a caught panic yields the worst possible fitness; a
+// panicking batch is evaluated again one action vector at a time to find the culprits
+struct PanicGuard<'a, F: OptimizationFn> {
+    function: &'a F,
+    catch_panics: bool,
+    objective: Objective,
+    replication: u64,
+    panicked: RefCell<Vec<Vec<i32>>>,
+}
+
+impl<'a, F: OptimizationFn> PanicGuard<'a, F> {
+    fn new(function: &'a F, config: &GmabConfig, replication: u64) -> Self {
+        PanicGuard {
+            function,
+            catch_panics: config.catch_panics,
+            objective: config.objective,
+            replication,
+            panicked: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn call(&self, action_vector: &[i32]) -> f64 {
+        self.function
+            .evaluate_replication(action_vector, self.replication)
+    }
+
+    fn into_panicked(self) -> Vec<Vec<i32>> {
+        self.panicked.into_inner()
+    }
+}
+
+impl<F: OptimizationFn + RefUnwindSafe> OptimizationFn for PanicGuard<'_, F> {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        #[cfg(feature = "std")]
+        if self.catch_panics {
+            let (function, replication) = (self.function, self.replication);
+            return match catch_unwind(|| function.evaluate_replication(action_vector, replication))
+            {
+                Ok(fitness) => fitness,
+                Err(_) => {
+                    self.panicked.borrow_mut().push(action_vector.to_vec());
+                    self.objective.cost(f64::MAX)
+                }
+            };
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = (self.catch_panics, self.objective);
+        self.call(action_vector)
+    }
+
+    fn evaluate_batch(&self, action_vectors: &[Vec<i32>]) -> Vec<f64> {
+        #[cfg(feature = "std")]
+        if self.catch_panics {
+            if let Ok(fitnesses) = catch_unwind(|| self.function.evaluate_batch(action_vectors)) {
+                return fitnesses;
+            }
+            return action_vectors
+                .iter()
+                .map(|action_vector| self.evaluate(action_vector))
+                .collect();
+        }
+        self.function.evaluate_batch(action_vectors)
+    }
+
+    fn set_time(&self, time: usize) {
+        self.function.set_time(time);
+    }
+}
+
+#[cfg(feature = "std")]
+fn write_record_jsonl<W: std::io::Write>(
+    writer: &mut W,
+    record: &EvaluationRecord,
+) -> std::io::Result<()> {
+    let action: Vec<String> = record
+        .action_vector
+        .iter()
+        .map(|value| value.to_string())
+        .collect();
+    let fitness = if record.fitness.is_finite() {
+        record.fitness.to_string()
+    } else {
+        String::from("null")
+    };
+    writeln!(
+        writer,
+        "{{\"action\":[{}],\"fitness\":{},\"generation\":{},\"timestamp\":{}}}",
+        action.join(","),
+        fitness,
+        record.generation,
+        record.elapsed.as_secs_f64()
+    )
 }
 
-impl<F: OptimizationFn> Gmab<F> {
+// the worst fitness of panicking arms would dominate the worst observed cost
+fn observe_cost(worst_observed_cost: f64, cost: f64) -> f64 {
+    if cost == f64::MAX {
+        return worst_observed_cost;
+    }
+    worst_observed_cost.max(cost)
+}
+
+impl<F: OptimizationFn + RefUnwindSafe> Gmab<F> {
     fn get_arm_index(&self, individual: &Arm) -> i32 {
-        match self
-            .lookup_table
-            .get(&individual.get_action_vector().to_vec())
-        {
+        match self.lookup_table.get(individual.get_action_vector()) {
             Some(&index) => index,
             None => -1,
         }
     }
 
     pub fn new(opti_function: F, bounds: Vec<(i32, i32)>) -> Gmab<F> {
-        let dimension = bounds.len();
-        let lower_bound = bounds.iter().map(|&(low, _)| low).collect::<Vec<i32>>();
-        let upper_bound = bounds.iter().map(|&(_, high)| high).collect::<Vec<i32>>();
-        // Default values for the Genetic Algorithm
-        let population_size = 20; // Default population size
-        let mutation_rate = 0.25; // Default mutation rate
-        let crossover_rate = 1.0; // Default crossover rate
-        let mutation_span = 0.1; // Default mutation span
-
-        Gmab::new_with_parameter(
-            opti_function,
-            population_size,
-            mutation_rate,
-            crossover_rate,
-            mutation_span,
-            dimension,
-            lower_bound,
-            upper_bound,
-        )
+        Gmab::with_config(opti_function, GmabConfig::new(bounds))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_parameter(
         opti_function: F,
         population_size: usize,
@@ -55,36 +402,427 @@ impl<F: OptimizationFn> Gmab<F> {
         lower_bound: Vec<i32>,
         upper_bound: Vec<i32>,
     ) -> Gmab<F> {
-        let genetic_algorithm = GeneticAlgorithm::new(
+        debug_assert_eq!(dimension, lower_bound.len());
+
+        let config = GmabConfig::from_bounds(lower_bound, upper_bound)
+            .with_population_size(population_size)
+            .with_mutation_rate(mutation_rate)
+            .with_crossover_rate(crossover_rate)
+            .with_mutation_span(mutation_span);
+
+        Gmab::with_config(opti_function, config)
+    }
+
+    pub fn with_config(opti_function: F, config: GmabConfig) -> Gmab<F> {
+        Gmab::try_with_config(opti_function, config).expect("invalid configuration")
+    }
+
+    pub fn try_with_config(opti_function: F, config: GmabConfig) -> Result<Gmab<F>, GaError> {
+        let mut gmab = Gmab::try_with_config_deferred(opti_function, config)?;
+        gmab.initialize()?;
+        gmab.ensure_evaluated();
+        Ok(gmab)
+    }
+
+    // sets up the optimizer without creating the initial population, see initialize; the
+    // accessors of the best arm such as get_best_arm panic until the population has been
+    // evaluated by the first step
+    pub fn try_with_config_deferred(
+        opti_function: F,
+        mut config: GmabConfig,
+    ) -> Result<Gmab<F>, GaError> {
+        let mut genetic_algorithm = GeneticAlgorithm::new(
             opti_function,
-            population_size,
-            mutation_rate,
-            crossover_rate,
-            mutation_span,
-            dimension,
-            lower_bound,
-            upper_bound,
+            config.population_size,
+            config.mutation_rate,
+            config.crossover_rate,
+            config.mutation_span,
+            config.dimension(),
+            config.lower_bound.clone(),
+            config.upper_bound.clone(),
         );
+        genetic_algorithm.set_mutation_distribution(config.mutation_distribution);
+        genetic_algorithm.set_pairing(config.pairing);
+        genetic_algorithm.set_odd_crossover(config.odd_crossover);
+        genetic_algorithm.set_parent_selection(config.parent_selection, config.fitness_scaling);
+        genetic_algorithm.set_tournament(config.tournament_size, config.tournament_replacement);
+        genetic_algorithm.set_saturation_threshold(config.saturation_threshold);
+        genetic_algorithm.set_max_resample_attempts(config.max_resample_attempts);
+        genetic_algorithm.set_min_distance(config.min_distance);
+        genetic_algorithm.set_allow_duplicates(config.allow_duplicates);
+        genetic_algorithm.set_crossover_dedup(config.crossover_dedup);
+        genetic_algorithm.set_initializer(config.initializer.as_ref().map(|slot| slot.0.clone()));
+        if let Some(steps) = &config.steps {
+            genetic_algorithm.set_steps(steps.clone());
+        }
+        if config.record_trace && config.seed.is_none() {
+            config.seed = Some(genetic_algorithm.rng.gen());
+        }
+        if let Some(seed) = config.seed {
+            genetic_algorithm.set_seed(seed);
+        }
+
+        let search_space_size = genetic_algorithm.search_space_size();
+        let mut shrunk_population_from = None;
+        if config.shrink_to_search_space && search_space_size < genetic_algorithm.population_size {
+            shrunk_population_from = Some(genetic_algorithm.population_size);
+            genetic_algorithm.population_size = search_space_size;
+        }
+        // crossover mates pairs of individuals
+        if genetic_algorithm.population_size < 2 {
+            return Err(GaError::PopulationTooSmall {
+                size: genetic_algorithm.population_size,
+            });
+        }
+
+        Ok(Gmab {
+            sample_average_tree: SortedMultiMap::new(),
+            arm_memory: Vec::new(),
+            lookup_table: Map::new(),
+            genetic_algorithm,
+            config,
+            simulations_used: 0,
+            fitness_cache: Map::new(),
+            initial_population: None,
+            population_evaluated: false,
+            best_fitness_history: Vec::new(),
+            stagnant_generations: 0,
+            generations_elapsed: 0,
+            generation_start: 0,
+            last_population_fitness: Vec::new(),
+            best_arm_trajectory: Vec::new(),
+            wall_time: Duration::ZERO,
+            shrunk_population_from,
+            panicked_inputs: Vec::new(),
+            on_evaluate: None,
+            prefetched: Map::new(),
+            trace_rewards: Vec::new(),
+            history: Vec::new(),
+            #[cfg(feature = "std")]
+            history_stream: None,
+            #[cfg(feature = "std")]
+            created: Instant::now(),
+            surrogate: None,
+            screened_offspring: 0,
+            evaluation_timing: EvaluationTiming::default(),
+            worst_observed_cost: f64::MIN,
+            injected_indexes: Vec::new(),
+            protected_indexes: Vec::new(),
+            crossover_improvements: 0,
+            mutation_improvements: 0,
+        })
+    }
 
-        let mut arm_memory: Vec<Arm> = Vec::new();
-        let mut lookup_table: HashMap<Vec<i32>, i32> = HashMap::new();
-        let mut sample_average_tree: SortedMultiMap<FloatKey, i32> = SortedMultiMap::new();
+    // generates the initial population with the configured initializer without evaluating
+    // it, see get_individuals; step and run evaluate it first (not charged to the budget)
+    // and initialize on their own if necessary
+    pub fn initialize(&mut self) -> Result<(), GaError> {
+        if self.population_evaluated {
+            return Err(GaError::AlreadyEvaluated);
+        }
+        self.initial_population = Some(self.genetic_algorithm.generate_new_population()?);
+        Ok(())
+    }
+
+    fn ensure_evaluated(&mut self) {
+        if self.population_evaluated {
+            return;
+        }
+        if self.initial_population.is_none() {
+            self.initialize().expect("invalid configuration");
+        }
+        let initial_population = self.initial_population.take().unwrap();
+        self.evaluate_initial_population(initial_population);
+        self.population_evaluated = true;
+    }
 
-        let mut initial_population = genetic_algorithm.generate_new_population();
+    fn evaluate_initial_population(&mut self, mut initial_population: Vec<Arm>) {
+        let batch_rewards: Option<Vec<f64>> = self.config.batch_evaluation.then(|| {
+            let action_vectors: Vec<Vec<i32>> = initial_population
+                .iter()
+                .map(|individual| individual.get_action_vector().to_vec())
+                .collect();
+            self.guarded(0, |guard| guard.evaluate_batch(&action_vectors))
+        });
 
+        // the initial population is evaluated once up front and is not charged to the budget
         for (index, individual) in initial_population.iter_mut().enumerate() {
-            individual.pull(&genetic_algorithm.opti_function);
-            arm_memory.push(individual.clone());
-            lookup_table.insert(individual.get_action_vector().to_vec(), index as i32);
-            sample_average_tree.insert(FloatKey::new(individual.get_mean_reward()), index as i32);
+            let reward = match &batch_rewards {
+                Some(batch_rewards) => {
+                    individual.record_reward(batch_rewards[index]);
+                    batch_rewards[index]
+                }
+                None => self.guarded(0, |guard| individual.pull(guard)),
+            };
+            if self.config.record_trace {
+                self.trace_rewards.push(reward);
+            }
+            self.record_evaluation(individual.get_action_vector(), reward);
+            if self.config.caches_fitness() {
+                self.fitness_cache.insert(
+                    individual.get_action_vector().to_vec(),
+                    CachedFitness::new(reward),
+                );
+            }
+            self.worst_observed_cost =
+                observe_cost(self.worst_observed_cost, self.config.objective.cost(reward));
+
+            // duplicates (if allowed) add a pull to the arm they repeat
+            if let Some(&index) = self.lookup_table.get(individual.get_action_vector()) {
+                self.sample_average_tree.delete(
+                    &FloatKey::new(self.mean_cost(&self.arm_memory[index as usize])),
+                    &index,
+                );
+                self.arm_memory[index as usize].record_reward(reward);
+                self.sample_average_tree.insert(
+                    FloatKey::new(self.mean_cost(&self.arm_memory[index as usize])),
+                    index,
+                );
+                continue;
+            }
+
+            let index = self.arm_memory.len() as i32;
+            self.arm_memory.push(individual.clone());
+            self.lookup_table
+                .insert(individual.get_action_vector().to_vec(), index);
+            self.sample_average_tree
+                .insert(FloatKey::new(self.mean_cost(individual)), index);
         }
+    }
 
-        Gmab {
-            sample_average_tree,
-            arm_memory,
-            lookup_table,
-            genetic_algorithm,
+    // calls the objective function through a PanicGuard and keeps the inputs it panicked on
+    fn guarded<R>(&mut self, replication: u64, call: impl FnOnce(&PanicGuard<F>) -> R) -> R {
+        let guard = PanicGuard::new(
+            &self.genetic_algorithm.opti_function,
+            &self.config,
+            replication,
+        );
+        let result = call(&guard);
+        self.panicked_inputs.extend(guard.into_panicked());
+        result
+    }
+
+    pub fn get_simulations_used(&self) -> usize {
+        self.simulations_used
+    }
+
+    // keeps the evaluation with record_history and writes it to the history stream
+    fn record_evaluation(&mut self, action_vector: &[i32], fitness: f64) {
+        #[cfg(feature = "std")]
+        let streaming = self.history_stream.is_some();
+        #[cfg(not(feature = "std"))]
+        let streaming = false;
+        if !self.config.record_history && !streaming {
+            return;
+        }
+
+        #[cfg(feature = "std")]
+        let elapsed = self.created.elapsed();
+        #[cfg(not(feature = "std"))]
+        let elapsed = Duration::ZERO;
+        let record = EvaluationRecord {
+            action_vector: action_vector.to_vec(),
+            fitness,
+            generation: self.generations_elapsed,
+            elapsed,
+        };
+
+        #[cfg(feature = "std")]
+        if let Some(stream) = &mut self.history_stream {
+            if stream.error.is_none() {
+                stream.error = write_record_jsonl(&mut stream.writer, &record).err();
+            }
+        }
+        if self.config.record_history {
+            self.history.push(record);
+        }
+    }
+
+    // Every evaluation of the optimization function goes through here so that
+    // the budget accounting and the fitness cache stay consistent.
+    fn evaluate(&mut self, arm_action_vector: &[i32]) -> f64 {
+        // inactive genes do not reach the cache or the optimization function
+        let action_vector = &self
+            .genetic_algorithm
+            .canonical_action_vector(arm_action_vector);
+        if let Some(fitness) = self.cached_fitness(action_vector) {
+            return fitness;
+        }
+
+        let fitness = match self.take_prefetched(action_vector) {
+            Some(fitness) => fitness,
+            None => {
+                #[cfg(feature = "std")]
+                let evaluation_start = self.config.evaluation_timing.then(Instant::now);
+                let replication = match self.lookup_table.get(arm_action_vector) {
+                    Some(&arm_index) => self.arm_memory[arm_index as usize].get_num_pulls() as u64,
+                    None => 0,
+                };
+                let fitness = self.guarded(replication, |guard| guard.evaluate(action_vector));
+                #[cfg(feature = "std")]
+                if let Some(evaluation_start) = evaluation_start {
+                    self.evaluation_timing.record(evaluation_start.elapsed());
+                }
+                fitness
+            }
+        };
+        self.simulations_used += 1;
+        if self.config.record_trace {
+            self.trace_rewards.push(fitness);
+        }
+        self.record_evaluation(action_vector, fitness);
+        self.worst_observed_cost = observe_cost(
+            self.worst_observed_cost,
+            self.config.objective.cost(fitness),
+        );
+
+        if let Some(on_evaluate) = &mut self.on_evaluate {
+            on_evaluate(action_vector, fitness);
+        }
+        if let Some(surrogate) = &mut self.surrogate {
+            surrogate.update(action_vector, fitness);
+        }
+
+        if self.config.caches_fitness() {
+            self.fitness_cache
+                .insert(action_vector.to_vec(), CachedFitness::new(fitness));
+        }
+
+        fitness
+    }
+
+    // expired entries count as misses and are overwritten by the next evaluation
+    fn cached_fitness(&self, action_vector: &[i32]) -> Option<f64> {
+        if !self.config.caches_fitness() {
+            return None;
+        }
+        self.fitness_cache
+            .get(action_vector)
+            .filter(|cached| cached.is_fresh(self.config.effective_cache_ttl()))
+            .map(|cached| cached.fitness)
+    }
+
+    fn take_prefetched(&mut self, action_vector: &[i32]) -> Option<f64> {
+        let rewards = self.prefetched.get_mut(action_vector)?;
+        let fitness = rewards.remove(0);
+        if rewards.is_empty() {
+            self.prefetched.remove(action_vector);
+        }
+        Some(fitness)
+    }
+
+    // batch simulations the generation did not use were still run, they are charged to the
+    // budget and dropped
+    fn charge_prefetched(&mut self) {
+        let unused: usize = self.prefetched.values().map(Vec::len).sum();
+        self.simulations_used += unused;
+        self.prefetched.clear();
+    }
+
+    // simulates the offspring and population members evaluated by the coming generation in
+    // one batch, as far as the budget allows; rewards not consumed by the generation are
+    // charged by charge_prefetched
+    fn prefetch_generation(
+        &mut self,
+        offspring: &[Arm],
+        max_offspring: usize,
+        current_indexes: &[i32],
+        population: &[Arm],
+    ) {
+        let allowance = self.generation_allowance();
+        let offspring: Vec<&Arm> = offspring
+            .iter()
+            .filter(|individual| !current_indexes.contains(&self.get_arm_index(individual)))
+            .take(max_offspring)
+            .collect();
+        // a binding cap spreads the replications by the bandit policy, which is not known
+        // in advance
+        let population: &[Arm] = if offspring.len() + population.len() > allowance
+            && self.config.max_simulations_per_generation.is_some()
+        {
+            &[]
+        } else {
+            population
+        };
+        let candidates = offspring.into_iter().chain(population);
+
+        let mut action_vectors: Vec<Vec<i32>> = Vec::new();
+        for individual in candidates {
+            if action_vectors.len() >= allowance {
+                break;
+            }
+            let action_vector = self
+                .genetic_algorithm
+                .canonical_action_vector(individual.get_action_vector());
+            // cached vectors are not simulated again
+            if self.config.caches_fitness()
+                && (self.cached_fitness(&action_vector).is_some()
+                    || action_vectors.contains(&action_vector))
+            {
+                continue;
+            }
+            action_vectors.push(action_vector);
+        }
+
+        if action_vectors.is_empty() {
+            return;
+        }
+
+        #[cfg(feature = "std")]
+        let evaluation_start = self.config.evaluation_timing.then(Instant::now);
+        let rewards = self.guarded(0, |guard| guard.evaluate_batch(&action_vectors));
+        #[cfg(feature = "std")]
+        if let Some(evaluation_start) = evaluation_start {
+            self.evaluation_timing
+                .record_batch(evaluation_start.elapsed(), action_vectors.len());
+        }
+        for (action_vector, reward) in action_vectors.into_iter().zip(rewards) {
+            self.prefetched
+                .entry(action_vector)
+                .or_default()
+                .push(reward);
+        }
+    }
+
+    // durations of the simulations charged to the budget, empty unless enabled in the config
+    pub fn evaluation_timing(&self) -> &EvaluationTiming {
+        &self.evaluation_timing
+    }
+
+    // called with the action vector and fitness of every simulation charged to the budget
+    pub fn set_on_evaluate(
+        &mut self,
+        on_evaluate: impl FnMut(&[i32], f64) + Send + Sync + 'static,
+    ) {
+        self.on_evaluate = Some(Box::new(on_evaluate));
+    }
+
+    // only the `screened_offspring` offspring with the best predicted fitness are evaluated,
+    // the surrogate is first trained on the mean rewards of all arms seen so far
+    pub fn set_surrogate(
+        &mut self,
+        mut surrogate: impl Surrogate + Send + Sync + 'static,
+        screened_offspring: usize,
+    ) {
+        for arm in &self.arm_memory {
+            surrogate.update(arm.get_action_vector(), arm.get_mean_reward());
         }
+        self.surrogate = Some(Box::new(surrogate));
+        self.screened_offspring = screened_offspring;
+    }
+
+    pub fn fitness_of(&mut self, arm: &Arm) -> f64 {
+        self.evaluate(arm.get_action_vector())
+    }
+
+    // inactive genes are set to their lower bound before arm lookup, caching and evaluation,
+    // arms differing only in inactive genes share their evaluations
+    pub fn set_activity_mask(
+        &mut self,
+        activity_mask: impl Fn(&[i32]) -> Vec<bool> + Send + Sync + 'static,
+    ) {
+        self.genetic_algorithm
+            .set_activity_mask(Box::new(activity_mask));
     }
 
     fn max_number_pulls(&self) -> i32 {
@@ -97,9 +835,19 @@ impl<F: OptimizationFn> Gmab<F> {
         max_number_pulls
     }
 
+    // order of two arms with equal scores in the selection policies, Less preferring `a`
+    fn tie_break(&self, a: &Arm, b: &Arm) -> Ordering {
+        let by_action = a.get_action_vector().cmp(b.get_action_vector());
+        match self.config.tie_break {
+            TieBreak::FewerPulls => a.get_num_pulls().cmp(&b.get_num_pulls()).then(by_action),
+            TieBreak::MorePulls => b.get_num_pulls().cmp(&a.get_num_pulls()).then(by_action),
+            TieBreak::LexSmallestAction => by_action,
+        }
+    }
+
     fn find_best_ucb(&self, simulations_used: usize) -> i32 {
-        let arm_index_ucb_norm_min: i32 = *self.sample_average_tree.iter().next().unwrap().1;
-        let ucb_norm_min: f64 = self.arm_memory[arm_index_ucb_norm_min as usize].get_mean_reward();
+        let arm_index_ucb_norm_min: i32 = self.best_arm_index();
+        let ucb_norm_min: f64 = self.mean_cost(&self.arm_memory[arm_index_ucb_norm_min as usize]);
 
         let max_number_pulls = self.max_number_pulls();
 
@@ -108,7 +856,7 @@ impl<F: OptimizationFn> Gmab<F> {
         for (_ucb_norm, arm_index) in self.sample_average_tree.iter() {
             ucb_norm_max = f64::max(
                 ucb_norm_max,
-                self.arm_memory[*arm_index as usize].get_mean_reward(),
+                self.mean_cost(&self.arm_memory[*arm_index as usize]),
             );
 
             // checks if we are still in the non dominated-set (current mean <= mean_max_pulls)
@@ -122,21 +870,26 @@ impl<F: OptimizationFn> Gmab<F> {
         let mut best_ucb_value: f64 = f64::MAX;
 
         for (_ucb_norm, arm_index) in self.sample_average_tree.iter() {
-            if ucb_norm_max == ucb_norm_min {
-                best_arm_index = *arm_index;
-            }
-
             // transform sample mean to interval [0,1]
-            let transformed_sample_mean: f64 =
-                (self.arm_memory[*arm_index as usize].get_mean_reward() - ucb_norm_min)
-                    / (ucb_norm_max - ucb_norm_min);
+            let transformed_sample_mean: f64 = if ucb_norm_max > ucb_norm_min {
+                (self.mean_cost(&self.arm_memory[*arm_index as usize]) - ucb_norm_min)
+                    / (ucb_norm_max - ucb_norm_min)
+            } else {
+                0.0
+            };
             let penalty_term: f64 = (2.0 * (simulations_used as f64).ln()
                 / self.arm_memory[*arm_index as usize].get_num_pulls() as f64)
                 .sqrt();
             let ucb_value: f64 = transformed_sample_mean + penalty_term;
 
             // new best solution found
-            if ucb_value < best_ucb_value {
+            if ucb_value < best_ucb_value
+                || (ucb_value == best_ucb_value
+                    && self.tie_break(
+                        &self.arm_memory[*arm_index as usize],
+                        &self.arm_memory[best_arm_index as usize],
+                    ) == Ordering::Less)
+            {
                 best_arm_index = *arm_index;
                 best_ucb_value = ucb_value;
             }
@@ -150,277 +903,4100 @@ impl<F: OptimizationFn> Gmab<F> {
         best_arm_index
     }
 
-    fn sample_and_update(&mut self, arm_index: i32, mut individual: Arm) {
+    // variance-aware counterpart of find_best_ucb (UCB-V): arms whose rewards are noisy get a
+    // wider confidence term than equally pulled arms with consistent rewards
+    pub fn select_ucb_v(&self, arms: &[Arm], total_pulls: u32) -> usize {
+        assert!(!arms.is_empty(), "cannot select from an empty set of arms");
+
+        let mean_min = arms
+            .iter()
+            .map(|arm| self.mean_cost(arm))
+            .fold(f64::MAX, f64::min);
+        let mean_max = arms
+            .iter()
+            .map(|arm| self.mean_cost(arm))
+            .fold(f64::MIN, f64::max);
+        // rewards are transformed to roughly [0,1] like in find_best_ucb
+        let scale = if mean_max > mean_min {
+            mean_max - mean_min
+        } else {
+            1.0
+        };
+        let log_pulls = (total_pulls.max(1) as f64).ln();
+
+        let mut best_arm_index = 0;
+        let mut best_ucb_value = f64::MAX;
+
+        for (arm_index, arm) in arms.iter().enumerate() {
+            let num_pulls = arm.get_num_pulls() as f64;
+            if num_pulls == 0.0 {
+                continue;
+            }
+
+            let transformed_sample_mean = (self.mean_cost(arm) - mean_min) / scale;
+            let transformed_variance = arm.get_reward_variance() / (scale * scale);
+            let penalty_term = (2.0 * transformed_variance * log_pulls / num_pulls).sqrt()
+                + 3.0 * log_pulls / num_pulls;
+            let ucb_value = transformed_sample_mean + penalty_term;
+
+            if ucb_value < best_ucb_value
+                || (ucb_value == best_ucb_value
+                    && self.tie_break(arm, &arms[best_arm_index]) == Ordering::Less)
+            {
+                best_arm_index = arm_index;
+                best_ucb_value = ucb_value;
+            }
+        }
+
+        best_arm_index
+    }
+
+    fn sample_and_update(&mut self, arm_index: i32, mut individual: Arm) -> f64 {
         if arm_index >= 0 {
+            // a cached fitness is no new observation, the arm is not pulled again
+            let action_vector = self
+                .genetic_algorithm
+                .canonical_action_vector(individual.get_action_vector());
+            if let Some(fitness) = self.cached_fitness(&action_vector) {
+                return fitness;
+            }
             self.sample_average_tree.delete(
-                &FloatKey::new(self.arm_memory[arm_index as usize].get_mean_reward()),
+                &FloatKey::new(self.mean_cost(&self.arm_memory[arm_index as usize])),
                 &arm_index,
             );
-            self.arm_memory[arm_index as usize].pull(&self.genetic_algorithm.opti_function);
+            let reward = self.evaluate(individual.get_action_vector());
+            self.arm_memory[arm_index as usize].record_reward(reward);
             self.sample_average_tree.insert(
-                FloatKey::new(self.arm_memory[arm_index as usize].get_mean_reward()),
+                FloatKey::new(self.mean_cost(&self.arm_memory[arm_index as usize])),
                 arm_index,
             );
+            reward
         } else {
-            individual.pull(&self.genetic_algorithm.opti_function);
+            let reward = self.evaluate(individual.get_action_vector());
+            individual.record_reward(reward);
             self.arm_memory.push(individual.clone());
             self.lookup_table.insert(
                 individual.get_action_vector().to_vec(),
                 self.arm_memory.len() as i32 - 1,
             );
             self.sample_average_tree.insert(
-                FloatKey::new(individual.get_mean_reward()),
+                FloatKey::new(self.mean_cost(&individual)),
                 self.arm_memory.len() as i32 - 1,
             );
+            reward
         }
     }
 
-    pub fn optimize(&mut self, simulation_budget: usize) -> Vec<i32> {
-        let verbose = false;
-        let mut simulation_used: usize = 0;
-        loop {
-            let mut current_indexes: Vec<i32> = Vec::new();
-            let mut population: Vec<Arm> = Vec::new();
+    pub fn get_best_arm(&self) -> Arm {
+        self.arm_memory[self.find_best_ucb(self.simulations_used) as usize].clone()
+    }
 
-            // get first self.population_size elements from sorted tree and use value to get arm
-            self.sample_average_tree
-                .iter()
-                .take(self.genetic_algorithm.population_size)
-                .for_each(|(_key, arm_index)| {
-                    population.push(self.arm_memory[*arm_index as usize].clone());
-                    current_indexes.push(*arm_index);
-                });
+    pub fn best_arm_confidence_interval(&self, confidence: f64) -> (f64, f64) {
+        self.get_best_arm().get_confidence_interval(confidence)
+    }
 
-            // shuffle population
-            population.shuffle(&mut rand::thread_rng());
+    // expected improvement of the arm's mean over the incumbent's in the minimized sense,
+    // both means being normal with their standard errors
+    pub fn expected_improvement(&self, arm: &Arm) -> f64 {
+        let incumbent = &self.arm_memory[self.best_arm_index() as usize];
 
-            let crossover_pop = self.genetic_algorithm.crossover(&population);
+        let squared_error =
+            |arm: &Arm| arm.get_reward_variance() / arm.get_num_pulls().max(1) as f64;
+        let improvement = self.mean_cost(incumbent) - self.mean_cost(arm);
+        let sigma = (squared_error(incumbent) + squared_error(arm)).sqrt();
+        if sigma == 0.0 {
+            return improvement.max(0.0);
+        }
+        let z = improvement / sigma;
+        let density = (-0.5 * z * z).exp() / (2.0 * core::f64::consts::PI).sqrt();
+        improvement * normal_cdf(z) + sigma * density
+    }
 
-            // mutate automatically removes duplicates
-            let mutated_pop = self.genetic_algorithm.mutate(&crossover_pop);
+    // one-sided z-test of the incumbent against the runner-up; arms with fewer than two pulls
+    // have no variance estimate and are never significant
+    pub fn best_is_significant(&self, confidence: f64) -> bool {
+        assert!(
+            confidence > 0.0 && confidence < 1.0,
+            "confidence must lie in (0, 1)"
+        );
+        let mut ranked = self
+            .sample_average_tree
+            .iter()
+            .map(|(_key, arm_index)| &self.arm_memory[*arm_index as usize]);
+        let (best, runner_up) = match (ranked.next(), ranked.next()) {
+            (Some(best), Some(runner_up)) => (best, runner_up),
+            _ => return false,
+        };
+        if best.get_num_pulls() < 2 || runner_up.get_num_pulls() < 2 {
+            return false;
+        }
 
-            for individual in mutated_pop {
-                let arm_index = self.get_arm_index(&individual);
+        let squared_error = |arm: &Arm| arm.get_reward_variance() / arm.get_num_pulls() as f64;
+        let difference = self.mean_cost(runner_up) - self.mean_cost(best);
+        let standard_error = (squared_error(best) + squared_error(runner_up)).sqrt();
+        if standard_error == 0.0 {
+            return difference > 0.0;
+        }
+        difference / standard_error > normal_quantile(confidence)
+    }
 
-                // check if arm is in current population
-                if current_indexes.contains(&arm_index) {
-                    continue;
-                }
+    fn best_action_vector(&self) -> Vec<i32> {
+        self.get_best_arm().get_action_vector().to_vec()
+    }
 
-                self.sample_and_update(arm_index, individual.clone());
-                simulation_used += 1;
+    fn budget_exhausted(&self) -> bool {
+        self.simulations_used >= self.config.simulation_budget
+    }
 
-                if simulation_used >= simulation_budget {
-                    return self.arm_memory[self.find_best_ucb(simulation_used) as usize]
-                        .get_action_vector()
-                        .to_vec();
-                }
-            }
+    // sample average in the minimized sense, the key of an arm in the tree
+    fn mean_cost(&self, arm: &Arm) -> f64 {
+        self.config.objective.cost(arm.get_mean_reward())
+    }
 
-            for individual in population {
-                let arm_index = self.get_arm_index(&individual);
-                self.sample_and_update(arm_index, individual.clone());
-                simulation_used += 1;
+    // arm of the best sample average currently in the tree
+    fn best_arm_index(&self) -> i32 {
+        let (_key, arm_index) = self
+            .sample_average_tree
+            .iter()
+            .next()
+            .expect("population has not been evaluated yet");
+        *arm_index
+    }
 
-                if simulation_used >= simulation_budget {
-                    return self.arm_memory[self.find_best_ucb(simulation_used) as usize]
-                        .get_action_vector()
-                        .to_vec();
-                }
-            }
+    fn best_mean_reward(&self) -> f64 {
+        self.arm_memory[self.best_arm_index() as usize].get_mean_reward()
+    }
 
-            if verbose {
-                let best_arm_index = self.find_best_ucb(simulation_used);
-                print!(
-                    "x: {:?}",
-                    self.arm_memory[best_arm_index as usize].get_action_vector()
-                );
-                // get averaged function value over 50 simulations
-                let mut sum = 0.0;
-                for _ in 0..50 {
-                    sum += self.arm_memory[best_arm_index as usize]
-                        .get_function_value(&self.genetic_algorithm.opti_function);
-                }
-                print!(" f(x): {:.3}", sum / 50.0);
+    fn is_improvement(&self, candidate: f64, incumbent: f64) -> bool {
+        let objective = self.config.objective;
+        self.lowers_cost(objective.cost(candidate), objective.cost(incumbent))
+    }
 
-                print!(" n: {}", simulation_used);
-                // print number of pulls of best arm
-                println!(
-                    " n(x): {}",
-                    self.arm_memory[best_arm_index as usize].get_num_pulls()
-                );
+    // cost comparisons of the selection and replacement logic, differences within
+    // fitness_epsilon are noise
+    fn lowers_cost(&self, candidate_cost: f64, incumbent_cost: f64) -> bool {
+        candidate_cost < incumbent_cost - self.config.fitness_epsilon
+    }
+
+    fn record_best_fitness(&mut self, best_fitness: f64) {
+        let objective = self.config.objective;
+        let incumbent = self.best_fitness_history.iter().copied().reduce(|a, b| {
+            if objective.cost(b) < objective.cost(a) {
+                b
+            } else {
+                a
+            }
+        });
+
+        match incumbent {
+            Some(incumbent) if !self.is_improvement(best_fitness, incumbent) => {
+                self.stagnant_generations += 1
             }
+            _ => self.stagnant_generations = 0,
         }
+
+        self.best_fitness_history.push(best_fitness);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn get_best_fitness_history(&self) -> &[f64] {
+        &self.best_fitness_history
+    }
 
-    #[test]
-    fn test_sorted_multi_map_insert() {
-        let mut map = SortedMultiMap::new();
+    // fraction of the current search space evaluated at least once; a space too large to
+    // count reports 1.0 and is flagged by `search_space_is_countable`
+    pub fn coverage_fraction(&self) -> f64 {
+        if !self.search_space_is_countable() {
+            return 1.0;
+        }
+        let search_space_size = self.genetic_algorithm.search_space_size();
+        let evaluated = self
+            .arm_memory
+            .iter()
+            .filter(|arm| arm.get_num_pulls() > 0)
+            .count();
+        // arms evaluated before the bounds were zoomed may lie outside the current space
+        (evaluated as f64 / search_space_size as f64).min(1.0)
+    }
+
+    // share of the variance of the mean rewards of all arms explained by the value of each
+    // gene (correlation ratio), near 1 for genes the fitness depends on strongly
+    pub fn dimension_sensitivity(&self) -> Vec<f64> {
+        let dimension = self.config.dimension();
+        let n = self.arm_memory.len() as f64;
+        let mean = self
+            .arm_memory
+            .iter()
+            .map(|arm| arm.get_mean_reward())
+            .sum::<f64>()
+            / n;
+        let total_variation: f64 = self
+            .arm_memory
+            .iter()
+            .map(|arm| (arm.get_mean_reward() - mean).powi(2))
+            .sum();
+        if total_variation == 0.0 {
+            return vec![0.0; dimension];
+        }
+
+        (0..dimension)
+            .map(|j| {
+                let mut groups: Map<i32, (f64, usize)> = Map::new();
+                for arm in &self.arm_memory {
+                    let group = groups.entry(arm.get_action_vector()[j]).or_default();
+                    group.0 += arm.get_mean_reward();
+                    group.1 += 1;
+                }
+                let explained: f64 = groups
+                    .values()
+                    .map(|&(sum, count)| count as f64 * (sum / count as f64 - mean).powi(2))
+                    .sum();
+                explained / total_variation
+            })
+            .collect()
+    }
+
+    pub fn search_space_is_countable(&self) -> bool {
+        self.genetic_algorithm.search_space_size() < usize::MAX
+    }
+
+    pub fn get_generations_elapsed(&self) -> usize {
+        self.generations_elapsed
+    }
+
+    pub fn get_stagnant_generations(&self) -> usize {
+        self.stagnant_generations
+    }
+
+    // average improvement of the best fitness per 1000 simulations over the run
+    pub fn convergence_rate(&self) -> f64 {
+        let (Some(first_best), Some(last_best)) = (
+            self.best_fitness_history.first(),
+            self.best_fitness_history.last(),
+        ) else {
+            return 0.0;
+        };
+        if self.simulations_used == 0 {
+            return 0.0;
+        }
+
+        let objective = self.config.objective;
+        (objective.cost(*first_best) - objective.cost(*last_best)) / self.simulations_used as f64
+            * 1000.0
+    }
+
+    // variance of the fitness values observed when the population was last re-evaluated
+    pub fn population_fitness_variance(&self) -> f64 {
+        let n = self.last_population_fitness.len();
+        if n == 0 {
+            return 0.0;
+        }
+
+        let mean = self.last_population_fitness.iter().sum::<f64>() / n as f64;
+        self.last_population_fitness
+            .iter()
+            .map(|fitness| (fitness - mean).powi(2))
+            .sum::<f64>()
+            / n as f64
+    }
+
+    // the population consists of the population_size arms with the best sample average,
+    // injected arms take the place of the worst ones
+    fn population_indexes(&self) -> Vec<i32> {
+        let population_size = self.genetic_algorithm.population_size;
+        let protected = self.protected_indexes.len();
+        let injected: Vec<i32> = self
+            .injected_indexes
+            .iter()
+            .copied()
+            .filter(|arm_index| !self.protected_indexes.contains(arm_index))
+            .take(population_size - protected)
+            .collect();
+
+        let mut indexes: Vec<i32> = self
+            .sample_average_tree
+            .iter()
+            .map(|(_key, arm_index)| *arm_index)
+            .filter(|arm_index| {
+                !injected.contains(arm_index) && !self.protected_indexes.contains(arm_index)
+            })
+            // arms left outside by set_bounds
+            .filter(|arm_index| self.within_bounds(&self.arm_memory[*arm_index as usize]))
+            .take(population_size - protected - injected.len())
+            .collect();
+        indexes.extend(injected);
+        indexes.extend(&self.protected_indexes);
+        indexes
+    }
+
+    // temperature of Boltzmann selection at the current point of the budget
+    fn selection_temperature(&self) -> Option<f64> {
+        match self.config.parent_selection {
+            ParentSelection::Boltzmann {
+                initial_temperature,
+                final_temperature,
+            } => {
+                let progress = (self.simulations_used as f64
+                    / self.config.simulation_budget.max(1) as f64)
+                    .min(1.0);
+                Some(initial_temperature * (final_temperature / initial_temperature).powf(progress))
+            }
+            _ => None,
+        }
+    }
+
+    fn annealing_temperature(&self) -> Option<f64> {
+        let initial_temperature = self.config.annealing_temperature?;
+        let progress = self.simulations_used as f64 / self.config.simulation_budget.max(1) as f64;
+        Some(initial_temperature * (1.0 - progress).max(0.0))
+    }
+
+    // Metropolis criterion for an offspring whose cost exceeds the incumbent's by `delta`,
+    // a change within fitness_epsilon is accepted like a worsening by epsilon
+    fn metropolis_accept(&mut self, delta: f64, temperature: f64) -> bool {
+        if self.lowers_cost(delta, 0.0) || delta == 0.0 && self.config.fitness_epsilon == 0.0 {
+            return true;
+        }
+        if temperature <= 0.0 {
+            return false;
+        }
+        let delta = delta.max(self.config.fitness_epsilon);
+        self.genetic_algorithm.rng.gen::<f64>() < (-delta / temperature).exp()
+    }
+
+    // replaces the random number generator by a fresh one seeded with `seed`, the seed of
+    // the configuration (and thus of a recorded trace) is left unchanged
+    pub fn reseed(&mut self, seed: u64) {
+        self.genetic_algorithm.set_seed(seed);
+    }
+
+    // keeps gene `index` at `value` in every individual created from now on
+    pub fn freeze_dimension(&mut self, index: usize, value: i32) {
+        self.genetic_algorithm.freeze_gene(index, value);
+    }
+
+    pub fn unfreeze_dimension(&mut self, index: usize) {
+        self.genetic_algorithm.unfreeze_gene(index);
+    }
+
+    pub fn set_distance_metric(
+        &mut self,
+        distance_metric: impl Fn(&[i32], &[i32]) -> f64 + Send + Sync + 'static,
+    ) {
+        self.genetic_algorithm
+            .set_distance_metric(Box::new(distance_metric));
+    }
+
+    pub fn set_mutation_perturbation(
+        &mut self,
+        perturbation: impl Fn(f64, &mut dyn RngCore) -> f64 + Send + Sync + 'static,
+    ) {
+        self.genetic_algorithm
+            .set_perturbation(Box::new(perturbation));
+    }
+
+    pub fn get_population_size(&self) -> usize {
+        self.genetic_algorithm.population_size
+    }
+
+    // the configured population size if it exceeded the search space and was shrunk, see
+    // with_shrink_to_search_space
+    pub fn population_shrunk_from(&self) -> Option<usize> {
+        self.shrunk_population_from
+    }
+
+    // action vectors the objective function panicked on, see with_catch_panics
+    pub fn panicked_inputs(&self) -> &[Vec<i32>] {
+        &self.panicked_inputs
+    }
+
+    pub fn get_collision_rate(&self) -> f64 {
+        self.genetic_algorithm.collision_rate()
+    }
+
+    // number of times the mutation span was expanded because of saturation
+    pub fn get_saturation_responses(&self) -> usize {
+        self.genetic_algorithm.get_saturation_responses()
+    }
+
+    pub fn get_mutation_span(&self) -> f64 {
+        self.genetic_algorithm.get_mutation_span()
+    }
+
+    pub fn get_mutation_rate(&self) -> f64 {
+        self.genetic_algorithm.get_mutation_rate()
+    }
+
+    // mutates every gene with this standard deviation in value units instead of the mutation
+    // span relative to its range
+    pub fn set_absolute_mutation_sigma(&mut self, sigma: Vec<f64>) {
+        assert_eq!(
+            sigma.len(),
+            self.config.lower_bound.len(),
+            "one sigma per dimension is required"
+        );
+        assert!(
+            sigma.iter().all(|&s| s >= 0.0),
+            "sigma must not be negative"
+        );
+        self.genetic_algorithm.set_absolute_mutation_sigma(sigma);
+    }
+
+    // the mutation span of the current generation translated to each dimension's range, or
+    // the absolute sigma if set
+    pub fn effective_mutation_sigma(&self) -> Vec<f64> {
+        self.genetic_algorithm.effective_mutation_sigma()
+    }
+
+    // DE/rand/1 donor built from three random individuals of `population` other than `target`
+    pub fn de_mutation(&mut self, target: &Arm, population: &[Arm], f: f64) -> Arm {
+        self.genetic_algorithm.de_mutation(target, population, f)
+    }
+
+    // trial vector copying a contiguous run of the donor's genes into the target, see
+    // de_mutation for the donor
+    pub fn exponential_crossover(
+        &mut self,
+        target: &Arm,
+        donor: &Arm,
+        crossover_probability: f64,
+    ) -> Arm {
+        self.genetic_algorithm
+            .exponential_crossover(target, donor, crossover_probability)
+    }
+
+    // child taking each gene from the fitter parent with a probability growing with the
+    // relative fitness gap, equally fit parents are crossed uniformly
+    pub fn fitness_weighted_crossover(
+        &mut self,
+        parent_1: &Arm,
+        fitness_1: f64,
+        parent_2: &Arm,
+        fitness_2: f64,
+    ) -> Arm {
+        let objective = self.config.objective;
+        self.genetic_algorithm.fitness_weighted_crossover(
+            parent_1,
+            objective.cost(fitness_1),
+            parent_2,
+            objective.cost(fitness_2),
+        )
+    }
+
+    // indexes into get_individuals() ordered from best to worst, `fitnesses` holding the
+    // fitness of each individual; ties are broken by action vector
+    pub fn rank_population(&self, fitnesses: &[f64]) -> Vec<usize> {
+        let population = self.get_individuals();
+        assert_eq!(
+            fitnesses.len(),
+            population.len(),
+            "one fitness per individual is required"
+        );
+
+        let costs: Vec<f64> = fitnesses
+            .iter()
+            .map(|fitness| self.config.objective.cost(*fitness))
+            .collect();
+        rank_order(&costs, &population)
+    }
+
+    pub fn population_diversity(&self) -> f64 {
+        self.genetic_algorithm.diversity(&self.get_individuals())
+    }
+
+    // the k best arms by mean reward among those pulled at least min_recommendation_pulls
+    // times, together with their reward variance and the half width of a 95% confidence
+    // interval of the mean, infinite for arms pulled only once
+    pub fn get_best_k_distinct(&self, k: usize) -> Vec<(Arm, f64, f64)> {
+        self.sample_average_tree
+            .iter()
+            .map(|(_key, arm_index)| &self.arm_memory[*arm_index as usize])
+            .filter(|arm| arm.get_num_pulls() >= self.config.min_recommendation_pulls)
+            .take(k)
+            .map(|arm| {
+                let (lower, upper) = arm.get_confidence_interval(0.95);
+                (
+                    arm.clone(),
+                    arm.get_reward_variance(),
+                    (upper - lower) / 2.0,
+                )
+            })
+            .collect()
+    }
+
+    // number of pulls of every arm evaluated so far, including the initial population
+    pub fn visit_histogram(&self) -> Map<Vec<i32>, u32> {
+        self.arm_memory
+            .iter()
+            .map(|arm| (arm.get_action_vector().to_vec(), arm.get_num_pulls() as u32))
+            .collect()
+    }
+
+    pub fn get_individuals(&self) -> Vec<Arm> {
+        if let Some(initial_population) = &self.initial_population {
+            return initial_population.clone();
+        }
+        self.population_indexes()
+            .iter()
+            .map(|arm_index| self.arm_memory[*arm_index as usize].clone())
+            .collect()
+    }
+
+    fn selection_reference(&self) -> Option<f64> {
+        self.config.objective_offset.map(|offset| match offset {
+            ObjectiveOffset::Auto => self.worst_observed_cost,
+            ObjectiveOffset::Fixed(offset) => offset,
+        })
+    }
+
+    // simulations the current generation may still spend
+    fn generation_allowance(&self) -> usize {
+        let cap = self
+            .config
+            .max_simulations_per_generation
+            .map_or(usize::MAX, |cap| {
+                cap.saturating_sub(self.simulations_used - self.generation_start)
+            });
+        self.remaining_simulations().min(cap)
+    }
+
+    // population member to replicate next: lowest optimistic cost (UCB1 with rewards scaled
+    // to the range of the population)
+    fn select_replication(&self, population: &[Arm]) -> usize {
+        let arms: Vec<&Arm> = population
+            .iter()
+            .map(|individual| &self.arm_memory[self.get_arm_index(individual) as usize])
+            .collect();
+        let costs: Vec<f64> = arms.iter().map(|arm| self.mean_cost(arm)).collect();
+        let cost_min = costs.iter().copied().fold(f64::MAX, f64::min);
+        let cost_max = costs.iter().copied().fold(f64::MIN, f64::max);
+        let scale = if cost_max > cost_min {
+            cost_max - cost_min
+        } else {
+            1.0
+        };
+        let total_pulls: i32 = arms.iter().map(|arm| arm.get_num_pulls()).sum();
+        let log_pulls = (total_pulls.max(1) as f64).ln();
+
+        let lower_bounds: Vec<f64> = arms
+            .iter()
+            .zip(&costs)
+            .map(|(arm, cost)| {
+                let num_pulls = arm.get_num_pulls().max(1) as f64;
+                (cost - cost_min) / scale - (2.0 * log_pulls / num_pulls).sqrt()
+            })
+            .collect();
+        (0..population.len())
+            .min_by(|&a, &b| {
+                lower_bounds[a]
+                    .total_cmp(&lower_bounds[b])
+                    .then_with(|| self.tie_break(arms[a], arms[b]))
+            })
+            .unwrap()
+    }
+
+    // costs used for parent selection, lowered by the novelty bonus if configured
+    fn selection_fitnesses(&self, population: &[Arm], costs: &[f64]) -> Vec<f64> {
+        let Some((weight, neighbours)) = self.config.novelty else {
+            return costs.to_vec();
+        };
+
+        population
+            .iter()
+            .zip(costs)
+            .enumerate()
+            .map(|(index, (individual, cost))| {
+                let mut distances: Vec<f64> = population
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_index, _)| *other_index != index)
+                    .map(|(_, other)| self.genetic_algorithm.distance(individual, other))
+                    .collect();
+                distances.sort_by(f64::total_cmp);
+                distances.truncate(neighbours);
+                let novelty = if distances.is_empty() {
+                    0.0
+                } else {
+                    distances.iter().sum::<f64>() / distances.len() as f64
+                };
+                cost - weight * novelty
+            })
+            .collect()
+    }
+
+    fn run_generation(&mut self) {
+        let (parents, offspring) = self.sample_generation();
+        self.charge_prefetched();
+
+        if self.config.replacement_mode == ReplacementMode::DeterministicCrowding {
+            self.injected_indexes = self.crowding_survivors(&parents, &offspring);
+        }
+        if self.gap_offspring().is_some() {
+            self.injected_indexes = offspring
+                .iter()
+                .map(|individual| self.get_arm_index(individual))
+                .collect();
+        }
+    }
+
+    // offspring per generation that replace the worst members of the population, None
+    // without a generation gap
+    fn gap_offspring(&self) -> Option<usize> {
+        let generation_gap = self.config.generation_gap;
+        if self.config.replacement_mode != ReplacementMode::Generational || generation_gap >= 1.0 {
+            return None;
+        }
+        let population_size = self.genetic_algorithm.population_size as f64;
+        Some((generation_gap * population_size).ceil() as usize)
+    }
+
+    // breeds the offspring of a generation and samples them followed by the population,
+    // returns the breeding parents and the offspring sampled
+    fn sample_generation(&mut self) -> (Vec<Arm>, Vec<Arm>) {
+        self.generation_start = self.simulations_used;
+        let current_indexes = self.population_indexes();
+        let population: Vec<Arm> = self.get_individuals();
+        // protected arms are re-evaluated but do not breed
+        let breeding: Vec<Arm> = population
+            .iter()
+            .filter(|arm| !self.protected_indexes.contains(&self.get_arm_index(arm)))
+            .cloned()
+            .collect();
+        let fitnesses: Vec<f64> = breeding.iter().map(|arm| self.mean_cost(arm)).collect();
+        let selection_fitnesses = self.selection_fitnesses(&breeding, &fitnesses);
+
+        let selection_reference = self.selection_reference();
+        self.genetic_algorithm
+            .set_selection_reference(selection_reference);
+        if let Some(temperature) = self.selection_temperature() {
+            self.genetic_algorithm
+                .set_selection_temperature(temperature);
+        }
+        let mating_pool = self
+            .genetic_algorithm
+            .select_mating_pool(&breeding, &selection_fitnesses);
+        let (crossover_pop, crossed) = self.genetic_algorithm.crossover_with_flags(&mating_pool);
+
+        // mutate automatically removes duplicates
+        let (mut mutated_pop, sources) = self.genetic_algorithm.mutate_with_sources(&crossover_pop);
+
+        // operators that changed each offspring: (crossover, mutation)
+        let mut provenance: Vec<(bool, bool)> = mutated_pop
+            .iter()
+            .zip(sources)
+            .map(|(individual, source)| (crossed[source], *individual != crossover_pop[source]))
+            .collect();
+
+        if let Some(surrogate) = &self.surrogate {
+            let mut predictions: Vec<(f64, Arm, (bool, bool))> = mutated_pop
+                .into_iter()
+                .zip(provenance)
+                .filter(|(arm, _)| !current_indexes.contains(&self.get_arm_index(arm)))
+                .map(|(arm, provenance)| {
+                    let prediction = surrogate.predict(arm.get_action_vector());
+                    (self.config.objective.cost(prediction), arm, provenance)
+                })
+                .collect();
+            predictions.sort_by(|a, b| a.0.total_cmp(&b.0));
+            (mutated_pop, provenance) = predictions
+                .into_iter()
+                .take(self.screened_offspring)
+                .map(|(_, arm, provenance)| (arm, provenance))
+                .unzip();
+        }
+
+        let max_offspring = match self.config.replacement_mode {
+            ReplacementMode::Generational => self.gap_offspring().unwrap_or(mutated_pop.len()),
+            ReplacementMode::DeterministicCrowding => mutated_pop.len(),
+            ReplacementMode::SteadyState { replace } => replace,
+        };
+        let mut offspring_sampled: Vec<Arm> = Vec::new();
+
+        let annealing_temperature = match self.config.replacement_mode {
+            ReplacementMode::SteadyState { .. } => self.annealing_temperature(),
+            ReplacementMode::Generational | ReplacementMode::DeterministicCrowding => None,
+        };
+        let worst_cost = fitnesses.iter().copied().fold(f64::MIN, f64::max);
+        self.injected_indexes.clear();
+
+        if self.config.batch_evaluation {
+            self.prefetch_generation(&mutated_pop, max_offspring, &current_indexes, &population);
+        }
+
+        for (individual, provenance) in mutated_pop.into_iter().zip(provenance) {
+            if offspring_sampled.len() >= max_offspring {
+                break;
+            }
+
+            let arm_index = self.get_arm_index(&individual);
+
+            // check if arm is in current population
+            if current_indexes.contains(&arm_index) {
+                continue;
+            }
+
+            let best_cost = self.config.objective.cost(self.best_mean_reward());
+            let fitness = self.sample_and_update(arm_index, individual.clone());
+            offspring_sampled.push(individual.clone());
+            // only this arm changed, a better best arm must be this offspring
+            if self.lowers_cost(
+                self.config.objective.cost(self.best_mean_reward()),
+                best_cost,
+            ) {
+                self.record_improvement(provenance);
+            }
+
+            if let Some(temperature) = annealing_temperature {
+                let delta = self.config.objective.cost(fitness) - worst_cost;
+                if delta > 0.0 && self.metropolis_accept(delta, temperature) {
+                    let arm_index = self.get_arm_index(&individual);
+                    self.injected_indexes.push(arm_index);
+                }
+            }
+
+            if self.generation_allowance() == 0 {
+                return (breeding, offspring_sampled);
+            }
+        }
+
+        self.last_population_fitness.clear();
+        if self.generation_allowance() < population.len() {
+            while self.generation_allowance() > 0 {
+                let individual = population[self.select_replication(&population)].clone();
+                let arm_index = self.get_arm_index(&individual);
+                let fitness = self.sample_and_update(arm_index, individual);
+                self.last_population_fitness.push(fitness);
+            }
+            return (breeding, offspring_sampled);
+        }
+        for individual in population {
+            let arm_index = self.get_arm_index(&individual);
+            let fitness = self.sample_and_update(arm_index, individual.clone());
+            self.last_population_fitness.push(fitness);
+
+            if self.budget_exhausted() {
+                break;
+            }
+        }
+        (breeding, offspring_sampled)
+    }
+
+    fn record_improvement(&mut self, (by_crossover, by_mutation): (bool, bool)) {
+        self.crossover_improvements += u32::from(by_crossover);
+        self.mutation_improvements += u32::from(by_mutation);
+    }
+
+    // number of offspring that became the new best arm when sampled, attributed to the
+    // operators that produced them as (crossover, mutation)
+    pub fn operator_improvement_counts(&self) -> (u32, u32) {
+        (self.crossover_improvements, self.mutation_improvements)
+    }
+
+    // deterministic crowding: every offspring competes with the most similar parent, each
+    // parent's slot is taken by the best of the parent and the offspring competing with it;
+    // the survivors are ordered from best to worst
+    fn crowding_survivors(&self, parents: &[Arm], offspring: &[Arm]) -> Vec<i32> {
+        let mut survivors: Vec<i32> = parents
+            .iter()
+            .map(|parent| self.get_arm_index(parent))
+            .collect();
+        if parents.is_empty() {
+            return survivors;
+        }
+
+        for individual in offspring {
+            let slot = (0..parents.len())
+                .min_by(|&a, &b| {
+                    let distance_a = self.genetic_algorithm.distance(individual, &parents[a]);
+                    let distance_b = self.genetic_algorithm.distance(individual, &parents[b]);
+                    distance_a.total_cmp(&distance_b)
+                })
+                .unwrap();
+            let arm_index = self.get_arm_index(individual);
+            let incumbent = &self.arm_memory[survivors[slot] as usize];
+            if self.lowers_cost(
+                self.mean_cost(&self.arm_memory[arm_index as usize]),
+                self.mean_cost(incumbent),
+            ) {
+                survivors[slot] = arm_index;
+            }
+        }
+
+        survivors.sort_by(|a, b| {
+            self.mean_cost(&self.arm_memory[*a as usize])
+                .total_cmp(&self.mean_cost(&self.arm_memory[*b as usize]))
+        });
+        survivors
+    }
+
+    // pooled standard deviation of `samples` repeated evaluations of the (up to) three best
+    // members of the population, charged to the budget
+    pub fn estimate_noise(&mut self, samples: usize) -> f64 {
+        assert!(samples >= 2, "at least two samples per arm are required");
+        self.ensure_evaluated();
+        let mut squared_deviations = 0.0;
+        let mut degrees_of_freedom = 0;
+        for individual in self.get_individuals().into_iter().take(3) {
+            let mut rewards = Vec::with_capacity(samples);
+            while rewards.len() < samples && !self.budget_exhausted() {
+                let arm_index = self.get_arm_index(&individual);
+                rewards.push(self.sample_and_update(arm_index, individual.clone()));
+            }
+            if rewards.len() < 2 {
+                break;
+            }
+            let mean = rewards.iter().sum::<f64>() / rewards.len() as f64;
+            squared_deviations += rewards.iter().map(|r| (r - mean).powi(2)).sum::<f64>();
+            degrees_of_freedom += rewards.len() - 1;
+        }
+        if degrees_of_freedom == 0 {
+            return f64::NAN;
+        }
+        (squared_deviations / degrees_of_freedom as f64).sqrt()
+    }
+
+    // evaluates a fresh random population (charged to the budget) and returns its fitnesses
+    // sorted in ascending order; the arms join the memory like any other sample
+    pub fn sample_initial_landscape(&mut self) -> Vec<f64> {
+        let population = self
+            .genetic_algorithm
+            .generate_new_population()
+            .expect("search space too small to sample a population");
+
+        let mut fitnesses = Vec::with_capacity(population.len());
+        for individual in population {
+            if self.budget_exhausted() {
+                break;
+            }
+            let arm_index = self.get_arm_index(&individual);
+            fitnesses.push(self.sample_and_update(arm_index, individual));
+        }
+        fitnesses.sort_by(f64::total_cmp);
+        fitnesses
+    }
+
+    // lag-1 autocorrelation of the fitness along a random walk of single grid steps from a
+    // random starting point, charged to the budget; near 1 on smooth landscapes and near 0
+    // on rugged ones, NaN if fewer than two points could be evaluated
+    pub fn estimate_ruggedness(&mut self, walk_length: usize) -> f64 {
+        let mut position = self.genetic_algorithm.random_individual();
+        let mut fitnesses = Vec::with_capacity(walk_length);
+        while fitnesses.len() < walk_length && !self.budget_exhausted() {
+            let arm_index = self.get_arm_index(&position);
+            fitnesses.push(self.sample_and_update(arm_index, position.clone()));
+
+            let neighbours = self.genetic_algorithm.neighbours(&position);
+            if neighbours.is_empty() {
+                break;
+            }
+            let next = self.genetic_algorithm.rng.gen_range(0..neighbours.len());
+            position = neighbours[next].clone();
+        }
+        if fitnesses.len() < 2 {
+            return f64::NAN;
+        }
+
+        let mean = fitnesses.iter().sum::<f64>() / fitnesses.len() as f64;
+        let variation: f64 = fitnesses.iter().map(|f| (f - mean).powi(2)).sum();
+        // a flat walk is as smooth as it gets
+        if variation == 0.0 {
+            return 1.0;
+        }
+        let covariation: f64 = fitnesses
+            .windows(2)
+            .map(|pair| (pair[0] - mean) * (pair[1] - mean))
+            .sum();
+        covariation / variation
+    }
+
+    // regular grid with steps_per_dim[j] equally spaced values in dimension j
+    fn grid_points(&self, steps_per_dim: &[usize]) -> Vec<Vec<i32>> {
+        assert_eq!(
+            steps_per_dim.len(),
+            self.config.dimension(),
+            "one step count per dimension is required"
+        );
+
+        let mut points: Vec<Vec<i32>> = vec![Vec::new()];
+        for (j, &steps) in steps_per_dim.iter().enumerate() {
+            let (lower, upper) = (self.config.lower_bound[j], self.config.upper_bound[j]);
+            let mut values: Vec<i32> = (0..steps)
+                .map(|k| {
+                    if steps == 1 {
+                        return lower;
+                    }
+                    let span = upper as f64 - lower as f64;
+                    self.genetic_algorithm
+                        .snap_to_grid(j, lower as f64 + span * k as f64 / (steps - 1) as f64)
+                })
+                .collect();
+            values.dedup();
+
+            points = points
+                .iter()
+                .flat_map(|point| {
+                    values.iter().map(move |&value| {
+                        let mut point = point.clone();
+                        point.push(value);
+                        point
+                    })
+                })
+                .collect();
+        }
+        points
+    }
+
+    // evaluates a regular grid within the bounds until the budget is exhausted, the best
+    // grid points then compete for a place in the population; returns the points evaluated
+    pub fn seed_from_grid(&mut self, steps_per_dim: &[usize]) -> usize {
+        let mut evaluated = 0;
+
+        for point in self.grid_points(steps_per_dim) {
+            if self.budget_exhausted() {
+                break;
+            }
+
+            let individual = Arm::new(&point);
+            let arm_index = self.get_arm_index(&individual);
+            self.sample_and_update(arm_index, individual);
+            evaluated += 1;
+        }
+
+        evaluated
+    }
+
+    pub fn step(&mut self) {
+        self.ensure_evaluated();
+        if self.budget_exhausted() {
+            return;
+        }
+
+        #[cfg(feature = "std")]
+        let generation_start = Instant::now();
+        self.run_generation();
+        self.generations_elapsed += 1;
+        self.genetic_algorithm
+            .opti_function
+            .set_time(self.generations_elapsed);
+        #[cfg(feature = "std")]
+        {
+            self.wall_time += generation_start.elapsed();
+        }
+
+        if self.config.local_search {
+            self.local_search();
+        }
+        if let Some((factor, period)) = self.config.zoom {
+            if self.generations_elapsed.is_multiple_of(period) {
+                self.zoom_bounds(factor);
+            }
+        }
+        if let Some((period, diversity)) = self.config.dynamic_tracking {
+            if self.generations_elapsed.is_multiple_of(period) {
+                self.track_shift(diversity);
+            }
+        }
+
+        let best_fitness = self.best_mean_reward();
+        self.record_best_fitness(best_fitness);
+
+        if let Some(patience) = self.config.restart_patience {
+            if self.stagnant_generations >= patience {
+                self.restart();
+            }
+        }
+
+        if self.config.record_trajectory {
+            let best_arm_index = self.find_best_ucb(self.simulations_used);
+            self.best_arm_trajectory
+                .push(self.arm_memory[best_arm_index as usize].clone());
+        }
+    }
+
+    // like step, but reports an exhausted budget instead of doing nothing; returns the
+    // population after the generation
+    pub fn try_step(&mut self) -> Result<Vec<Arm>, GaError> {
+        self.ensure_evaluated();
+        if self.budget_exhausted() {
+            return Err(GaError::BudgetExhausted {
+                budget: self.config.simulation_budget,
+            });
+        }
+        self.step();
+        Ok(self.get_individuals())
+    }
+
+    // current search bounds, narrower than the configured ones after zooming
+    pub fn get_bounds(&self) -> Vec<(i32, i32)> {
+        self.genetic_algorithm.bounds()
+    }
+
+    fn within_bounds(&self, arm: &Arm) -> bool {
+        arm.get_action_vector()
+            .iter()
+            .zip(self.config.lower_bound.iter().zip(&self.config.upper_bound))
+            .all(|(value, (lower, upper))| lower <= value && value <= upper)
+    }
+
+    // replaces the bounds, population members outside of them are projected onto the
+    // nearest grid point inside (evaluating new arms against the budget)
+    pub fn set_bounds(
+        &mut self,
+        lower_bound: Vec<i32>,
+        upper_bound: Vec<i32>,
+    ) -> Result<(), GaError> {
+        let dimension = self.config.dimension();
+        for bound in [&lower_bound, &upper_bound] {
+            if bound.len() != dimension {
+                return Err(GaError::DimensionMismatch {
+                    expected: dimension,
+                    actual: bound.len(),
+                });
+            }
+        }
+        for (index, (&lower, &upper)) in lower_bound.iter().zip(&upper_bound).enumerate() {
+            if lower > upper {
+                return Err(GaError::InvalidBounds {
+                    index,
+                    lower,
+                    upper,
+                });
+            }
+        }
+
+        let population = self.get_individuals();
+        self.config.lower_bound = lower_bound.clone();
+        self.config.upper_bound = upper_bound.clone();
+        self.genetic_algorithm.set_bounds(lower_bound, upper_bound);
+
+        self.injected_indexes.clear();
+        for individual in population {
+            if self.within_bounds(&individual) {
+                continue;
+            }
+            let genes: Vec<i32> = individual
+                .get_action_vector()
+                .iter()
+                .enumerate()
+                .map(|(j, &value)| self.genetic_algorithm.snap_to_grid(j, value as f64))
+                .collect();
+            let projected = Arm::new(&genes);
+            let mut arm_index = self.get_arm_index(&projected);
+            if arm_index < 0 {
+                if self.budget_exhausted() {
+                    continue;
+                }
+                self.sample_and_update(arm_index, projected.clone());
+                arm_index = self.get_arm_index(&projected);
+            }
+            if !self.injected_indexes.contains(&arm_index) {
+                self.injected_indexes.push(arm_index);
+            }
+        }
+        Ok(())
+    }
+
+    // keeps the arm in every population without breeding from it, e.g. as a baseline for
+    // comparison; a new arm is evaluated against the budget
+    pub fn protect_arm(&mut self, arm: Arm) -> Result<(), GaError> {
+        let arm = self.config.build_arm(arm.get_action_vector().to_vec())?;
+        self.ensure_evaluated();
+        let mut arm_index = self.get_arm_index(&arm);
+        if arm_index >= 0 && self.protected_indexes.contains(&arm_index) {
+            return Ok(());
+        }
+        if self.protected_indexes.len() + 3 > self.genetic_algorithm.population_size {
+            return Err(GaError::TooManyProtectedArms {
+                population_size: self.genetic_algorithm.population_size,
+            });
+        }
+        if arm_index < 0 {
+            self.sample_and_update(arm_index, arm.clone());
+            arm_index = self.get_arm_index(&arm);
+        }
+        self.protected_indexes.push(arm_index);
+        Ok(())
+    }
+
+    // shrinks the bounds used by initialization and mutation around the best arm
+    pub fn zoom_bounds(&mut self, factor: f64) {
+        self.ensure_evaluated();
+        let best_arm = self.arm_memory[self.best_arm_index() as usize].clone();
+        let limits: Vec<(i32, i32)> = self
+            .config
+            .lower_bound
+            .iter()
+            .copied()
+            .zip(self.config.upper_bound.iter().copied())
+            .collect();
+        self.genetic_algorithm.zoom(&best_arm, factor, &limits);
+    }
+
+    // evaluates externally suggested arms (charged to the budget), those better than the
+    // worst member of the population replace it in the next generation; returns how many
+    // were accepted
+    pub fn inject_candidates(&mut self, arms: Vec<Arm>) -> Result<usize, GaError> {
+        for arm in &arms {
+            self.config.build_arm(arm.get_action_vector().to_vec())?;
+        }
+        self.ensure_evaluated();
+        let worst_cost = self
+            .get_individuals()
+            .iter()
+            .map(|arm| self.mean_cost(arm))
+            .fold(f64::MIN, f64::max);
+
+        let mut accepted = 0;
+        for arm in arms {
+            if self.budget_exhausted() {
+                break;
+            }
+            let arm_index = self.get_arm_index(&arm);
+            self.sample_and_update(arm_index, arm.clone());
+
+            let arm_index = self.get_arm_index(&arm);
+            let cost = self.mean_cost(&self.arm_memory[arm_index as usize]);
+            if cost < worst_cost && !self.injected_indexes.contains(&arm_index) {
+                self.injected_indexes.push(arm_index);
+                accepted += 1;
+            }
+        }
+        Ok(accepted)
+    }
+
+    // first-improvement hill climb from the best arm over its grid neighbours, the
+    // evaluations are charged to the budget; returns whether a better arm was found
+    pub fn local_search(&mut self) -> bool {
+        self.ensure_evaluated();
+        let mut incumbent = self.arm_memory[self.best_arm_index() as usize].clone();
+        let mut improved = false;
+
+        'climb: loop {
+            for neighbour in self.genetic_algorithm.neighbours(&incumbent) {
+                if self.budget_exhausted() {
+                    break 'climb;
+                }
+                let arm_index = self.get_arm_index(&neighbour);
+                self.sample_and_update(arm_index, neighbour.clone());
+
+                let arm_index = self.get_arm_index(&neighbour);
+                let candidate = self.arm_memory[arm_index as usize].get_mean_reward();
+                if self.is_improvement(candidate, incumbent.get_mean_reward()) {
+                    incumbent = self.arm_memory[arm_index as usize].clone();
+                    improved = true;
+                    continue 'climb;
+                }
+            }
+            break;
+        }
+
+        improved
+    }
+
+    // replaces the population by fresh individuals according to the restart mode, only the
+    // best individual is kept; the new individuals are charged to the budget
+    pub fn restart(&mut self) {
+        self.ensure_evaluated();
+        let best_arm = self.arm_memory[self.best_arm_index() as usize].clone();
+        let mut newcomers = self.genetic_algorithm.population_size - 1;
+
+        let mut individuals = match self.config.restart_mode {
+            RestartMode::Random => Vec::new(),
+            RestartMode::AroundBest { spread } => {
+                self.genetic_algorithm
+                    .generate_around(&best_arm, spread, newcomers / 2)
+            }
+            RestartMode::Hyperparameters {
+                mutation_rate,
+                mutation_span,
+                reinit_fraction,
+            } => {
+                let rng = &mut self.genetic_algorithm.rng;
+                let mutation_rate = rng.gen_range(mutation_rate.0..=mutation_rate.1);
+                let mutation_span = rng.gen_range(mutation_span.0..=mutation_span.1);
+                self.genetic_algorithm.set_mutation_rate(mutation_rate);
+                self.genetic_algorithm.set_mutation_span(mutation_span);
+
+                newcomers = (newcomers as f64 * reinit_fraction).round() as usize;
+                Vec::new()
+            }
+        };
+        // a search space too small for a full random population leaves the restart smaller
+        let random_individuals = self
+            .genetic_algorithm
+            .generate_new_population()
+            .unwrap_or_default();
+        for individual in random_individuals {
+            if individuals.len() >= newcomers {
+                break;
+            }
+            if individual != best_arm && !individuals.contains(&individual) {
+                individuals.push(individual);
+            }
+        }
+
+        self.injected_indexes.clear();
+        for individual in individuals {
+            if self.budget_exhausted() {
+                break;
+            }
+            let arm_index = self.get_arm_index(&individual);
+            self.sample_and_update(arm_index, individual.clone());
+            self.injected_indexes.push(self.get_arm_index(&individual));
+        }
+
+        self.stagnant_generations = 0;
+    }
+
+    // the objective may have changed: all arms and their rewards are forgotten, the best
+    // members of the population are re-evaluated and the `diversity` share of it is replaced
+    // by random individuals, protected arms are always kept; skipped if the remaining budget
+    // cannot re-evaluate a whole population
+    fn track_shift(&mut self, diversity: f64) {
+        let population_size = self.genetic_algorithm.population_size;
+        if self.remaining_simulations() < population_size {
+            return;
+        }
+
+        let mut elite: Vec<Arm> = self
+            .population_indexes()
+            .into_iter()
+            .filter(|arm_index| !self.protected_indexes.contains(arm_index))
+            .map(|arm_index| self.arm_memory[arm_index as usize].clone())
+            .collect();
+        elite.sort_by(|a, b| self.mean_cost(a).total_cmp(&self.mean_cost(b)));
+        elite.truncate(elite.len() - (diversity * elite.len() as f64).round() as usize);
+        let protected: Vec<Arm> = self
+            .protected_indexes
+            .iter()
+            .map(|&arm_index| self.arm_memory[arm_index as usize].clone())
+            .collect();
+        let newcomers = population_size - elite.len() - protected.len();
+
+        self.arm_memory.clear();
+        self.lookup_table.clear();
+        self.sample_average_tree = SortedMultiMap::new();
+        self.fitness_cache.clear();
+        self.prefetched.clear();
+        self.injected_indexes.clear();
+        self.protected_indexes.clear();
+
+        for mut individual in elite {
+            individual.reset_statistics();
+            self.sample_and_update(-1, individual);
+        }
+        for mut individual in protected {
+            individual.reset_statistics();
+            self.sample_and_update(-1, individual.clone());
+            self.protected_indexes.push(self.get_arm_index(&individual));
+        }
+
+        let random_individuals = self
+            .genetic_algorithm
+            .generate_new_population()
+            .unwrap_or_default();
+        for individual in random_individuals {
+            if self.injected_indexes.len() >= newcomers {
+                break;
+            }
+            if self.get_arm_index(&individual) >= 0 {
+                continue;
+            }
+            self.sample_and_update(-1, individual.clone());
+            self.injected_indexes.push(self.get_arm_index(&individual));
+        }
+    }
+
+    // worst case: every offspring is new, followed by a re-evaluation of the whole population
+    fn generation_cost(&self) -> usize {
+        let population_size = self.genetic_algorithm.population_size;
+        let offspring = match self.config.replacement_mode {
+            ReplacementMode::Generational => self.gap_offspring().unwrap_or(population_size),
+            ReplacementMode::DeterministicCrowding => population_size,
+            ReplacementMode::SteadyState { replace } => replace.min(population_size),
+        };
+
+        offspring + self.population_indexes().len()
+    }
+
+    // a generation may overshoot the budget by a few simulations, the remainder is then zero
+    pub fn remaining_budget(&self) -> u64 {
+        self.remaining_simulations() as u64
+    }
+
+    fn remaining_simulations(&self) -> usize {
+        self.config
+            .simulation_budget
+            .saturating_sub(self.simulations_used)
+    }
+
+    // whether the remaining budget covers a full generation in the worst case, a smaller
+    // budget lets optimize stop in the middle of one
+    pub fn can_complete_generation(&self) -> bool {
+        self.generation_cost() <= self.remaining_simulations()
+    }
+
+    pub fn summary(&self) -> RunSummary {
+        let best_arm = self.get_best_arm();
+
+        RunSummary {
+            best_action_vector: best_arm.get_action_vector().to_vec(),
+            best_fitness: best_arm.get_mean_reward(),
+            simulations_used: self.simulations_used,
+            generations: self.generations_elapsed,
+            diversity: self.population_diversity(),
+            wall_time: self.wall_time,
+        }
+    }
+
+    pub fn best_arm_per_generation(&self) -> &[Arm] {
+        &self.best_arm_trajectory
+    }
+
+    // mean reward of the recommended arm after a full run with the given seed
+    fn seeded_best_fitness(opti_function: F, config: &GmabConfig, seed: u64) -> f64 {
+        let config = config.clone().with_seed(seed);
+        let simulation_budget = config.simulation_budget;
+
+        let mut gmab = Gmab::with_config(opti_function, config);
+        gmab.optimize(simulation_budget);
+        gmab.get_best_arm().get_mean_reward()
+    }
+
+    // runs both configurations once per seed, differences are taken as a - b in the sense
+    // of the objective of config_a (negative when config_a is better)
+    pub fn compare(
+        config_a: GmabConfig,
+        config_b: GmabConfig,
+        opti_function: F,
+        seeds: &[u64],
+    ) -> ComparisonResult
+    where
+        F: Clone,
+    {
+        assert!(!seeds.is_empty(), "at least one seed is required");
+
+        let objective = config_a.objective;
+        let differences: Vec<f64> = seeds
+            .iter()
+            .map(|&seed| {
+                let best_a = Gmab::seeded_best_fitness(opti_function.clone(), &config_a, seed);
+                let best_b = Gmab::seeded_best_fitness(opti_function.clone(), &config_b, seed);
+                objective.cost(best_a) - objective.cost(best_b)
+            })
+            .collect();
+
+        ComparisonResult::from_differences(&differences)
+    }
+
+    // runs `n` fresh instances of this configuration, seeded with base_seed, base_seed + 1, ...
+    pub fn run_replications(&self, n: usize, base_seed: u64) -> ReplicationSummary
+    where
+        F: Clone,
+    {
+        assert!(n > 0, "at least one replication is required");
+
+        let best_fitnesses: Vec<f64> = (0..n)
+            .map(|i| {
+                Gmab::seeded_best_fitness(
+                    self.genetic_algorithm.opti_function.clone(),
+                    &self.config,
+                    base_seed.wrapping_add(i as u64),
+                )
+            })
+            .collect();
+
+        ReplicationSummary::from_best_fitnesses(
+            &best_fitnesses,
+            self.config.target_fitness,
+            self.config.objective,
+        )
+    }
+
+    #[cfg(feature = "std")]
+    // evolves `islands` independent populations of this configuration on separate threads,
+    // island i is seeded with derive_seed(master_seed, i) so that runs are reproducible
+    pub fn run_islands(&self, islands: usize, master_seed: u64) -> Vec<Vec<Arm>>
+    where
+        F: Clone + Send,
+    {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..islands)
+                .map(|i| {
+                    let config = self
+                        .config
+                        .clone()
+                        .with_seed(derive_seed(master_seed, i as u64));
+                    let opti_function = self.genetic_algorithm.opti_function.clone();
+
+                    scope.spawn(move || {
+                        let simulation_budget = config.simulation_budget;
+                        let mut island = Gmab::with_config(opti_function, config);
+                        island.optimize(simulation_budget);
+                        island.get_individuals()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("island thread panicked"))
+                .collect()
+        })
+    }
+
+    // empty unless the run was configured to record its history
+    pub fn get_evaluation_history(&self) -> &[EvaluationRecord] {
+        &self.history
+    }
+
+    // one JSON object per evaluation and line: action, fitness (null if not finite),
+    // generation and timestamp in seconds since construction; dumps the history kept with
+    // record_history, see stream_history_jsonl to write it without keeping it in memory
+    #[cfg(feature = "std")]
+    pub fn write_history_jsonl<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        for record in &self.history {
+            write_record_jsonl(&mut writer, record)?;
+        }
+        Ok(())
+    }
+
+    // writes every following evaluation to `writer` in the format of write_history_jsonl as
+    // soon as it happens, independently of record_history
+    #[cfg(feature = "std")]
+    pub fn stream_history_jsonl(&mut self, writer: impl std::io::Write + Send + Sync + 'static) {
+        self.history_stream = Some(HistoryStream {
+            writer: Box::new(writer),
+            error: None,
+        });
+    }
+
+    // flushes and closes the history stream, returns the first error writing to it
+    #[cfg(feature = "std")]
+    pub fn finish_history_stream(&mut self) -> std::io::Result<()> {
+        match self.history_stream.take() {
+            Some(HistoryStream {
+                error: Some(error), ..
+            }) => Err(error),
+            Some(mut stream) => stream.writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    // None unless the run was configured to record a trace
+    pub fn run_trace(&self) -> Option<RunTrace> {
+        self.config.record_trace.then(|| RunTrace {
+            config: self.config.clone(),
+            rewards: self.trace_rewards.clone(),
+        })
+    }
+
+    pub fn run_state(&self) -> RunState {
+        RunState {
+            generations: self.generations_elapsed,
+            simulations_used: self.simulations_used,
+            best_fitness: self.best_mean_reward(),
+            stagnant_generations: self.stagnant_generations,
+            wall_time: self.wall_time,
+            objective: self.config.objective,
+        }
+    }
+
+    // raises the simulation budget so that a finished run can be continued by run or step
+    // with all accumulated state
+    pub fn extend_budget(&mut self, additional: u64) {
+        let additional = usize::try_from(additional).unwrap_or(usize::MAX);
+        self.config.simulation_budget = self.config.simulation_budget.saturating_add(additional);
+    }
+
+    // share of the simulation budget used so far
+    pub fn progress_fraction(&self) -> f64 {
+        if self.config.simulation_budget == 0 {
+            return 1.0;
+        }
+        (self.simulations_used as f64 / self.config.simulation_budget as f64).min(1.0)
+    }
+
+    // progress towards whichever comes first, the simulation budget or `criterion`
+    pub fn progress_fraction_with(&self, criterion: &impl StoppingCriterion) -> f64 {
+        let criterion_progress = criterion.progress(&self.run_state()).unwrap_or(0.0);
+        self.progress_fraction().max(criterion_progress)
+    }
+
+    // runs generations until the criterion is met, the configured simulation budget
+    // still bounds the run
+    pub fn run(&mut self, criterion: &mut impl StoppingCriterion) -> Vec<i32> {
+        self.ensure_evaluated();
+        while !self.budget_exhausted() && !criterion.should_stop(&self.run_state()) {
+            let simulations_before = self.simulations_used;
+            self.step();
+
+            if self.simulations_used == simulations_before {
+                break;
+            }
+        }
+
+        self.best_action_vector()
+    }
+
+    pub fn optimize(&mut self, simulation_budget: usize) -> Vec<i32> {
+        // progress is printed to stdout, which only exists with std
+        #[cfg(feature = "std")]
+        let verbose = false;
+        self.config.simulation_budget = simulation_budget;
+        self.ensure_evaluated();
+
+        while !self.budget_exhausted() {
+            let simulations_before = self.simulations_used;
+            self.step();
+
+            // with the fitness cache enabled a generation may consist of cache hits only,
+            // in which case no further progress is possible
+            if self.simulations_used == simulations_before {
+                break;
+            }
+
+            #[cfg(feature = "std")]
+            if verbose {
+                let best_arm_index = self.find_best_ucb(self.simulations_used);
+                print!(
+                    "x: {:?}",
+                    self.arm_memory[best_arm_index as usize].get_action_vector()
+                );
+                // get averaged function value over 50 simulations
+                let mut sum = 0.0;
+                for _ in 0..50 {
+                    sum += self.arm_memory[best_arm_index as usize]
+                        .get_function_value(&self.genetic_algorithm.opti_function);
+                }
+                print!(" f(x): {:.3}", sum / 50.0);
+
+                print!(" n: {}", self.simulations_used);
+                // print number of pulls of best arm
+                println!(
+                    " n(x): {}",
+                    self.arm_memory[best_arm_index as usize].get_num_pulls()
+                );
+            }
+        }
+
+        self.best_action_vector()
+    }
+
+    // lazily runs one generation per item until the budget is exhausted or a generation
+    // makes no progress, like optimize
+    pub fn generations(&mut self) -> Generations<'_, F> {
+        Generations { gmab: self }
+    }
+}
+
+// state after a generation, yielded by Gmab::generations
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub simulations_used: usize,
+    pub best_fitness: f64,
+    pub diversity: f64,
+}
+
+pub struct Generations<'a, F: OptimizationFn> {
+    gmab: &'a mut Gmab<F>,
+}
+
+impl<F: OptimizationFn + RefUnwindSafe> Iterator for Generations<'_, F> {
+    type Item = GenerationStats;
+
+    fn next(&mut self) -> Option<GenerationStats> {
+        let gmab = &mut *self.gmab;
+        if gmab.budget_exhausted() {
+            return None;
+        }
+        let simulations_before = gmab.simulations_used;
+        gmab.step();
+        if gmab.simulations_used == simulations_before {
+            return None;
+        }
+
+        Some(GenerationStats {
+            generation: gmab.generations_elapsed,
+            simulations_used: gmab.simulations_used,
+            best_fitness: gmab.best_mean_reward(),
+            diversity: gmab.population_diversity(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unnecessary_to_owned, clippy::useless_vec)]
+mod tests {
+    use super::*;
+    use crate::config::{FitnessScaling, OddCrossover};
+    use alloc::string::ToString;
+    use alloc::{format, vec};
+
+    #[test]
+    fn test_sorted_multi_map_insert() {
+        let mut map = SortedMultiMap::new();
         map.insert(FloatKey::new(1.0), 1);
         map.insert(FloatKey::new(1.0), 2);
         map.insert(FloatKey::new(2.0), 3);
 
-        let mut iter = map.iter();
+        let mut iter = map.iter();
+
+        assert_eq!(iter.next(), Some((&FloatKey::new(1.0), &1)));
+        assert_eq!(iter.next(), Some((&FloatKey::new(1.0), &2)));
+        assert_eq!(iter.next(), Some((&FloatKey::new(2.0), &3)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_sorted_multi_map_delete() {
+        let mut map = SortedMultiMap::new();
+        map.insert(FloatKey::new(1.0), 1);
+        map.insert(FloatKey::new(1.0), 2);
+        map.insert(FloatKey::new(2.0), 3);
+
+        assert!(map.delete(&FloatKey::new(1.0), &1));
+        assert!(map.delete(&FloatKey::new(2.0), &3));
+        assert!(!map.delete(&FloatKey::new(2.0), &3));
+
+        let mut iter = map.iter();
+
+        assert_eq!(iter.next(), Some((&FloatKey::new(1.0), &2)));
+        assert_eq!(iter.next(), None);
+    }
+
+    fn mock_opti_function(_vec: &[i32]) -> f64 {
+        0.0
+    }
+
+    #[test]
+    fn test_gmab_new() {
+        let gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        assert_eq!(gmab.genetic_algorithm.population_size, 10);
+        assert_eq!(gmab.arm_memory.len(), 10);
+        assert_eq!(gmab.lookup_table.len(), 10);
+
+        // check if there are 10  elements in sample_average_tree
+        let mut count = 0;
+        for _ in gmab.sample_average_tree.iter() {
+            count += 1;
+        }
+        assert_eq!(count, 10);
+    }
+
+    #[test]
+    fn test_gmab_get_arm_index_with_existing() {
+        let mut gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        let arm = Arm::new(&vec![1, 2]);
+        gmab.arm_memory.push(arm.clone());
+        gmab.lookup_table
+            .insert(arm.get_action_vector().to_vec(), 0);
+        assert_eq!(gmab.get_arm_index(&arm), 0);
+    }
+
+    #[test]
+    fn test_gmab_max_number_pulls() {
+        let gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        assert_eq!(gmab.max_number_pulls(), 1);
+    }
+
+    #[test]
+    fn test_gmab_find_best_ucb() {
+        let gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        assert_eq!(gmab.find_best_ucb(100), 0);
+    }
+
+    #[test]
+    fn test_gmab_find_best_ucb_with_existing() {
+        let mut gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+
+        let arm = Arm::new(&vec![1, 2]);
+        gmab.arm_memory.push(arm.clone());
+        gmab.lookup_table
+            .insert(arm.get_action_vector().to_vec(), 0);
+
+        let arm2 = Arm::new(&vec![1, 2]);
+        gmab.arm_memory.push(arm2.clone());
+        gmab.lookup_table
+            .insert(arm2.get_action_vector().to_vec(), 1);
+
+        gmab.sample_and_update(0, arm.clone());
+        gmab.sample_and_update(1, arm2.clone());
+
+        assert_eq!(gmab.find_best_ucb(100), 0);
+    }
+
+    #[test]
+    fn test_gmab_sample_and_update_with_existing() {
+        let mut gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+
+        let arm = Arm::new(&vec![1, 2]);
+        gmab.arm_memory.push(arm.clone());
+        gmab.lookup_table
+            .insert(arm.get_action_vector().to_vec(), 0);
+
+        gmab.sample_and_update(0, arm.clone());
+
+        assert_eq!(gmab.arm_memory[0].get_num_pulls(), 2);
+        assert_eq!(gmab.arm_memory[0].get_mean_reward(), 0.0);
+        assert_eq!(
+            gmab.lookup_table.get(&arm.get_action_vector().to_vec()),
+            Some(&0)
+        );
+    }
+
+    #[test]
+    fn test_gmab_fitness_of_counts_simulation() {
+        let mut gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+
+        let arm = Arm::new(&[1, 2]);
+        assert_eq!(gmab.fitness_of(&arm), 0.0);
+        assert_eq!(gmab.get_simulations_used(), 1);
+
+        gmab.fitness_of(&arm);
+        assert_eq!(gmab.get_simulations_used(), 2);
+    }
+
+    #[test]
+    fn test_gmab_fitness_of_cache_hit() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(10)
+            .with_fitness_cache(true);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+
+        let arm = Arm::new(&[11, 12]);
+        assert_eq!(gmab.fitness_of(&arm), 23.0);
+        assert_eq!(gmab.get_simulations_used(), 1);
+
+        // second evaluation is served from the cache and costs nothing
+        assert_eq!(gmab.fitness_of(&arm), 23.0);
+        assert_eq!(gmab.get_simulations_used(), 1);
+
+        // resampling a cached arm of the population neither costs nor pulls
+        let individual = gmab.get_individuals()[0].clone();
+        let arm_index = gmab.get_arm_index(&individual);
+        let pulls = gmab.arm_memory[arm_index as usize].get_num_pulls();
+        gmab.sample_and_update(arm_index, individual);
+        assert_eq!(gmab.arm_memory[arm_index as usize].get_num_pulls(), pulls);
+        assert_eq!(gmab.get_simulations_used(), 1);
+    }
+
+    #[test]
+    fn test_gmab_stagnation_respects_fitness_epsilon() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(10)
+            .with_fitness_epsilon(1e-6);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        gmab.record_best_fitness(10.0);
+        assert_eq!(gmab.get_stagnant_generations(), 0);
+
+        // an improvement below epsilon does not count as progress
+        gmab.record_best_fitness(10.0 - 1e-9);
+        assert_eq!(gmab.get_stagnant_generations(), 1);
+
+        gmab.record_best_fitness(9.0);
+        assert_eq!(gmab.get_stagnant_generations(), 0);
+        assert_eq!(gmab.get_best_fitness_history().len(), 3);
+    }
+
+    #[test]
+    fn test_gmab_step_records_best_fitness() {
+        let mut gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+
+        gmab.step();
+        gmab.step();
+        assert_eq!(gmab.get_best_fitness_history(), &[0.0, 0.0]);
+        assert_eq!(gmab.get_stagnant_generations(), 1);
+    }
+
+    #[test]
+    fn test_gmab_population_fitness_variance() {
+        let mut gmab = Gmab::new_with_parameter(
+            mock_opti_function,
+            10,
+            0.1,
+            0.9,
+            0.5,
+            2,
+            vec![0, 0],
+            vec![10, 10],
+        );
+        assert_eq!(gmab.population_fitness_variance(), 0.0);
+
+        gmab.last_population_fitness = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(gmab.population_fitness_variance(), 4.0);
+    }
+
+    #[test]
+    fn test_gmab_population_fitness_variance_after_step() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(10);
+        let mut gmab = Gmab::with_config(|_x: &[i32]| 3.0, config);
+
+        gmab.step();
+        assert_eq!(gmab.last_population_fitness.len(), 10);
+        assert_eq!(gmab.population_fitness_variance(), 0.0);
+    }
+
+    #[test]
+    fn test_gmab_steady_state_replaces_limited_individuals() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(10)
+            .with_replacement_mode(ReplacementMode::SteadyState { replace: 2 });
+        // injective function, so ties cannot reorder the population
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 100 + x[1]) as f64, config);
+
+        for _ in 0..5 {
+            let before = gmab.get_individuals();
+            gmab.step();
+            let after = gmab.get_individuals();
+
+            assert_eq!(after.len(), 10);
+            let changed = after.iter().filter(|arm| !before.contains(arm)).count();
+            assert!(changed <= 2);
+        }
+    }
+
+    #[test]
+    fn test_gmab_population_diversity_with_custom_metric() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(10);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        assert!(gmab.population_diversity() > 0.0);
+
+        // all individuals are distinct, so a discrete metric yields exactly 1.0
+        gmab.set_distance_metric(|a: &[i32], b: &[i32]| if a == b { 0.0 } else { 1.0 });
+        assert_eq!(gmab.population_diversity(), 1.0);
+    }
+
+    #[test]
+    fn test_gmab_best_arm_per_generation() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(10)
+            .with_record_trajectory(true);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+
+        for _ in 0..4 {
+            gmab.step();
+        }
+
+        let trajectory = gmab.best_arm_per_generation();
+        assert_eq!(trajectory.len(), 4);
+        assert_eq!(trajectory.len(), gmab.get_best_fitness_history().len());
+
+        // without recording nothing is kept
+        let mut gmab = Gmab::new(mock_opti_function, vec![(0, 10), (0, 10)]);
+        gmab.step();
+        assert!(gmab.best_arm_per_generation().is_empty());
+    }
+
+    #[test]
+    fn test_gmab_seed_reproduces_run() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_seed(7);
+        let function = |x: &[i32]| ((x[0] - 30).pow(2) + (x[1] - 60).pow(2)) as f64;
+
+        let mut gmab_1 = Gmab::with_config(function, config.clone());
+        let mut gmab_2 = Gmab::with_config(function, config);
+
+        assert_eq!(gmab_1.optimize(300), gmab_2.optimize(300));
+        assert_eq!(gmab_1.get_individuals(), gmab_2.get_individuals());
+    }
+
+    #[test]
+    fn test_gmab_run_replications_deterministic_function() {
+        let config = GmabConfig::new(vec![(0, 3), (0, 3)])
+            .with_population_size(4)
+            .with_simulation_budget(200)
+            .with_target_fitness(0.0);
+        let gmab = Gmab::with_config(
+            |x: &[i32]| ((x[0] - 1).pow(2) + (x[1] - 2).pow(2)) as f64,
+            config,
+        );
+
+        let summary = gmab.run_replications(5, 42);
+        assert_eq!(summary.replications, 5);
+        assert_eq!(summary.mean_best_fitness, 0.0);
+        assert_eq!(summary.std_best_fitness, 0.0);
+        assert_eq!(summary.min_best_fitness, summary.max_best_fitness);
+        assert_eq!(summary.success_rate, Some(1.0));
+    }
+
+    #[test]
+    fn test_replication_summary_statistics() {
+        let summary = ReplicationSummary::from_best_fitnesses(
+            &[1.0, 2.0, 3.0, 6.0],
+            Some(2.0),
+            Objective::Minimize,
+        );
+        assert_eq!(summary.mean_best_fitness, 3.0);
+        assert_eq!(summary.std_best_fitness, (14.0f64 / 3.0).sqrt());
+        assert_eq!(summary.min_best_fitness, 1.0);
+        assert_eq!(summary.max_best_fitness, 6.0);
+        assert_eq!(summary.success_rate, Some(0.5));
+    }
+
+    #[test]
+    fn test_gmab_can_complete_generation() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)]).with_population_size(10);
+        let gmab = Gmab::with_config(mock_opti_function, config.clone());
+        assert!(gmab.can_complete_generation());
+
+        let gmab = Gmab::with_config(mock_opti_function, config.with_simulation_budget(5));
+        assert!(!gmab.can_complete_generation());
+    }
+
+    #[test]
+    fn test_gmab_can_complete_generation_steady_state() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_replacement_mode(ReplacementMode::SteadyState { replace: 2 })
+            .with_seed(3);
+        let population_size = Gmab::with_config(mock_opti_function, config.clone())
+            .get_individuals()
+            .len();
+
+        let gmab = Gmab::with_config(
+            mock_opti_function,
+            config.clone().with_simulation_budget(population_size + 2),
+        );
+        assert!(gmab.can_complete_generation());
+
+        let gmab = Gmab::with_config(
+            mock_opti_function,
+            config.with_simulation_budget(population_size + 1),
+        );
+        assert!(!gmab.can_complete_generation());
+    }
+
+    #[test]
+    fn test_gmab_select_ucb_v_prefers_low_variance() {
+        let gmab = Gmab::new(mock_opti_function, vec![(0, 10), (0, 10)]);
+
+        let mut noisy_arm = Arm::new(&[1, 1]);
+        let mut steady_arm = Arm::new(&[2, 2]);
+        for reward in [0.0, 10.0, 0.0, 10.0] {
+            noisy_arm.record_reward(reward);
+        }
+        for _ in 0..4 {
+            steady_arm.record_reward(5.0);
+        }
+
+        let arms = vec![noisy_arm, steady_arm];
+        assert_eq!(gmab.select_ucb_v(&arms, 8), 1);
+    }
+
+    #[test]
+    fn test_gmab_select_ucb_v_tie_break() {
+        let arm = |genes: &[i32], rewards: &[f64]| {
+            let mut arm = Arm::new(genes);
+            for &reward in rewards {
+                arm.record_reward(reward);
+            }
+            arm
+        };
+        // with a single total pull the confidence terms vanish and equal means tie
+        let arms = vec![
+            arm(&[5, 5], &[2.0]),
+            arm(&[1, 1], &[2.0, 2.0, 2.0]),
+            arm(&[3, 3], &[4.0, 4.0]),
+            arm(&[0, 0], &[2.0, 2.0]),
+        ];
+
+        let expected = [
+            (TieBreak::FewerPulls, 0),
+            (TieBreak::MorePulls, 1),
+            (TieBreak::LexSmallestAction, 3),
+        ];
+        for (tie_break, expected) in expected {
+            let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_tie_break(tie_break);
+            let gmab = Gmab::with_config(mock_opti_function, config);
+            assert_eq!(gmab.select_ucb_v(&arms, 1), expected);
+            // the order of the arms does not matter
+            let reversed: Vec<Arm> = arms.iter().rev().cloned().collect();
+            assert_eq!(gmab.select_ucb_v(&reversed, 1), arms.len() - 1 - expected);
+        }
+    }
+
+    #[test]
+    fn test_gmab_roulette_selection_with_scaling() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_parent_selection(ParentSelection::Roulette)
+            .with_fitness_scaling(FitnessScaling::SigmaScaling)
+            .with_seed(5);
+        let mut gmab = Gmab::with_config(
+            |x: &[i32]| ((x[0] - 30).pow(2) + (x[1] - 60).pow(2)) as f64,
+            config,
+        );
+
+        gmab.step();
+
+        // scores relative to the worst individual, scaled by their distance to the mean
+        // in units of twice the standard deviation
+        let population = gmab.get_individuals();
+        let costs: Vec<f64> = population.iter().map(|arm| gmab.mean_cost(arm)).collect();
+        let worst = costs.iter().copied().fold(f64::MIN, f64::max);
+        let scores: Vec<f64> = costs.iter().map(|cost| worst - cost).collect();
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        let sigma = (scores
+            .iter()
+            .map(|score| (score - mean).powi(2))
+            .sum::<f64>()
+            / scores.len() as f64)
+            .sqrt();
+        assert!(sigma > 1.0);
+        let weights: Vec<f64> = scores
+            .iter()
+            .map(|score| (1.0 + (score - mean) / (2.0 * sigma)).max(0.1))
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        let probabilities = gmab
+            .genetic_algorithm
+            .selection_probabilities(&population, &costs);
+        for (probability, weight) in probabilities.iter().zip(&weights) {
+            assert!((probability - weight / total).abs() < 1e-12);
+        }
+        // the scaling keeps the order of the costs
+        let best = costs.iter().copied().fold(f64::MAX, f64::min);
+        let probability_of =
+            |cost: f64| probabilities[costs.iter().position(|&c| c == cost).unwrap()];
+        assert!(probability_of(best) > probability_of(worst));
+
+        gmab.optimize(500);
+        assert!(gmab.get_simulations_used() >= 500);
+        assert!(gmab.get_best_fitness_history().len() > 1);
+    }
+
+    #[test]
+    fn test_gmab_convergence_rate() {
+        let mut gmab = Gmab::new(mock_opti_function, vec![(0, 10), (0, 10)]);
+        assert_eq!(gmab.convergence_rate(), 0.0);
+
+        for best_fitness in [100.0, 80.0, 70.0, 40.0] {
+            gmab.record_best_fitness(best_fitness);
+        }
+        gmab.simulations_used = 2000;
+
+        assert_eq!(gmab.convergence_rate(), 30.0);
+    }
+
+    #[test]
+    fn test_gmab_tracks_collision_rate() {
+        // 4 x 4 search space filled by a population of 8
+        let config = GmabConfig::new(vec![(0, 3), (0, 3)])
+            .with_population_size(8)
+            .with_mutation_rate(0.1)
+            .with_saturation_threshold(0.2)
+            .with_seed(11);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 10 + x[1]) as f64, config);
+
+        for _ in 0..10 {
+            gmab.step();
+        }
+
+        assert!(gmab.get_collision_rate() > 0.0);
+        assert!(gmab.get_saturation_responses() > 0);
+        assert!(gmab.get_mutation_span() > 0.1);
+    }
+
+    #[test]
+    fn test_gmab_try_with_config_resample_exhausted() {
+        let config = GmabConfig::new(vec![(0, 1), (0, 1)])
+            .with_population_size(10)
+            .with_max_resample_attempts(50);
+
+        assert_eq!(
+            Gmab::try_with_config(mock_opti_function, config).err(),
+            Some(GaError::ResampleExhausted { attempts: 50 })
+        );
+    }
+
+    #[test]
+    fn test_gmab_try_with_config_population_too_small() {
+        for size in [0, 1] {
+            let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(size);
+            assert_eq!(
+                Gmab::try_with_config(mock_opti_function, config).err(),
+                Some(GaError::PopulationTooSmall { size })
+            );
+        }
+
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(2);
+        assert!(Gmab::try_with_config(mock_opti_function, config).is_ok());
+    }
+
+    #[test]
+    fn test_gmab_summary() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_seed(2);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 1000 + x[1]) as f64 + 0.5, config);
+        let best_action_vector = gmab.optimize(200);
+
+        let summary = gmab.summary();
+        assert_eq!(summary.best_action_vector, best_action_vector);
+        assert_eq!(
+            summary.best_fitness,
+            (best_action_vector[0] * 1000 + best_action_vector[1]) as f64 + 0.5
+        );
+        assert_eq!(summary.simulations_used, gmab.get_simulations_used());
+        assert_eq!(summary.generations, gmab.get_best_fitness_history().len());
+        assert!(summary.generations > 0);
+        assert!(summary.diversity > 0.0);
+
+        let report = summary.to_string();
+        assert!(report.contains(&format!("{:.6}", summary.best_fitness)));
+        assert!(report.contains("simulations used"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_run_islands_reproducible() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(6)
+            .with_simulation_budget(120);
+        let gmab = Gmab::with_config(
+            |x: &[i32]| ((x[0] - 30).pow(2) + (x[1] - 60).pow(2)) as f64,
+            config,
+        );
+
+        let first_run = gmab.run_islands(3, 99);
+        let second_run = gmab.run_islands(3, 99);
+        assert_eq!(first_run.len(), 3);
+        assert_eq!(first_run, second_run);
+        assert_ne!(first_run[0], first_run[1]);
+
+        assert_ne!(derive_seed(99, 0), derive_seed(99, 1));
+        assert_ne!(derive_seed(99, 0), derive_seed(100, 0));
+    }
+
+    #[test]
+    fn test_gmab_rank_population() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(4)
+            .with_seed(8);
+        let gmab = Gmab::with_config(mock_opti_function, config.clone());
+        let population = gmab.get_individuals();
+
+        // individuals 1 and 3 tie and are ordered by their action vectors
+        let fitnesses = [3.0, 1.0, 0.5, 1.0];
+        let (first_tied, second_tied) =
+            if population[1].get_action_vector() < population[3].get_action_vector() {
+                (1, 3)
+            } else {
+                (3, 1)
+            };
+        assert_eq!(
+            gmab.rank_population(&fitnesses),
+            vec![2, first_tied, second_tied, 0]
+        );
+
+        let gmab = Gmab::with_config(
+            mock_opti_function,
+            config.with_objective(Objective::Maximize),
+        );
+        assert_eq!(
+            gmab.rank_population(&fitnesses),
+            vec![0, first_tied, second_tied, 2]
+        );
+    }
+
+    #[test]
+    fn test_gmab_maximize() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(6)
+            .with_objective(Objective::Maximize)
+            .with_seed(4);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 11 + x[1]) as f64, config);
+
+        let best = gmab.optimize(1000);
+        // the maximum is 120 at [10, 10]
+        assert!(best[0] * 11 + best[1] >= 110);
+        assert!(gmab.best_mean_reward() >= 110.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_activity_mask_end_to_end() {
+        use std::sync::{Arc, Mutex};
+
+        // the second gene only matters while the flag gene is 1, the third one never does
+        let evaluated = Arc::new(Mutex::new(Vec::<Vec<i32>>::new()));
+        let recorded = Arc::clone(&evaluated);
+        let function = move |x: &[i32]| {
+            recorded.lock().unwrap().push(x.to_vec());
+            match x[0] {
+                1 => (x[1] - 15).abs() as f64,
+                _ => 10.0,
+            }
+        };
+        let config = GmabConfig::new(vec![(0, 1), (0, 20), (0, 20)])
+            .with_population_size(6)
+            .with_seed(4);
+        let mut gmab = Gmab::with_config(function, config);
+        gmab.set_activity_mask(|x: &[i32]| vec![true, x[0] == 1, false]);
+        // the initial population was evaluated before the mask was set
+        evaluated.lock().unwrap().clear();
+
+        let best = gmab.optimize(300);
+        assert_eq!(best[0], 1);
+        assert!((best[1] - 15).abs() <= 2);
+
+        // full action vectors reach the function, inactive genes at their lower bound
+        let evaluated = evaluated.lock().unwrap();
+        assert!(evaluated.iter().all(|x| x.len() == 3));
+        assert!(evaluated.iter().all(|x| x[2] == 0));
+        assert!(evaluated.iter().all(|x| x[0] == 1 || x[1] == 0));
+    }
+
+    #[test]
+    fn test_gmab_activity_mask_shares_evaluations() {
+        let seeds = vec![vec![2, 1], vec![2, 2], vec![2, 3], vec![2, 4]];
+        let config = GmabConfig::new(vec![(0, 2), (0, 20)])
+            .with_population_size(4)
+            .with_initializer(crate::initializer::Seeded(seeds))
+            .with_fitness_cache(true);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 100 + x[1]) as f64, config);
+        gmab.set_activity_mask(|x: &[i32]| vec![true, x[0] != 0]);
+        let simulations_used = gmab.get_simulations_used();
+
+        // the arms only differ in their inactive second gene
+        assert_eq!(gmab.fitness_of(&Arm::new(&[0, 7])), 0.0);
+        assert_eq!(gmab.fitness_of(&Arm::new(&[0, 13])), 0.0);
+        assert_eq!(gmab.get_simulations_used(), simulations_used + 1);
+
+        assert_eq!(gmab.fitness_of(&Arm::new(&[1, 7])), 107.0);
+        assert_eq!(gmab.get_simulations_used(), simulations_used + 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_on_evaluate_sees_every_simulation() {
+        use std::sync::{Arc, Mutex};
+
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(6)
+            .with_fitness_cache(true);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 11 + x[1]) as f64, config);
+
+        let records = Arc::new(Mutex::new(Vec::<(Vec<i32>, f64)>::new()));
+        let collected = Arc::clone(&records);
+        gmab.set_on_evaluate(move |action_vector, fitness| {
+            collected
+                .lock()
+                .unwrap()
+                .push((action_vector.to_vec(), fitness));
+        });
+
+        gmab.optimize(200);
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), gmab.get_simulations_used());
+        for (action_vector, fitness) in records.iter() {
+            assert_eq!(*fitness, (action_vector[0] * 11 + action_vector[1]) as f64);
+        }
+    }
+
+    #[test]
+    fn test_gmab_metropolis_accept() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_seed(6);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        let accepted_hot = (0..1000)
+            .filter(|_| gmab.metropolis_accept(1.0, 10.0))
+            .count();
+        let accepted_cold = (0..1000)
+            .filter(|_| gmab.metropolis_accept(1.0, 1e-6))
+            .count();
+
+        // exp(-0.1) is about 0.9
+        assert!(accepted_hot > 800);
+        assert_eq!(accepted_cold, 0);
+        assert!(gmab.metropolis_accept(-1.0, 0.0));
+        assert!(gmab.metropolis_accept(0.0, 0.0));
+
+        // changes within the epsilon are no improvements and are rarely accepted when cold
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_fitness_epsilon(1.0)
+            .with_seed(6);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        let accepted_noise = (0..1000)
+            .filter(|_| gmab.metropolis_accept(-0.5, 1e-6))
+            .count();
+        assert_eq!(accepted_noise, 0);
+        assert!(gmab.metropolis_accept(-1.5, 1e-6));
+    }
+
+    #[test]
+    fn test_gmab_annealing_admits_worse_offspring() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_replacement_mode(ReplacementMode::SteadyState { replace: 4 })
+            .with_annealing(1e9)
+            .with_simulation_budget(1_000_000)
+            .with_seed(9);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 101 + x[1]) as f64, config);
+
+        let mut admitted = 0;
+        for _ in 0..10 {
+            gmab.step();
+            admitted += gmab.injected_indexes.len();
+            for arm_index in &gmab.injected_indexes {
+                assert!(gmab.population_indexes().contains(arm_index));
+            }
+            assert_eq!(gmab.get_individuals().len(), 10);
+        }
+        assert!(admitted > 0);
+    }
+
+    #[test]
+    fn test_gmab_seed_from_grid() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 90)])
+            .with_population_size(4)
+            .with_seed(12);
+        let function = |x: &[i32]| ((x[0] - 50).pow(2) + (x[1] - 30).pow(2)) as f64;
+        let mut gmab = Gmab::with_config(function, config);
+
+        let grid = gmab.grid_points(&[3, 4]);
+        assert_eq!(grid.len(), 12);
+        assert!(grid.contains(&vec![0, 0]));
+        assert!(grid.contains(&vec![50, 30]));
+        assert!(grid.contains(&vec![100, 90]));
+
+        assert_eq!(gmab.seed_from_grid(&[3, 4]), 12);
+        assert_eq!(gmab.get_simulations_used(), 12);
+        // the optimum lies on the grid and leads the population
+        assert_eq!(gmab.get_individuals()[0].get_action_vector(), &[50, 30]);
+    }
+
+    #[test]
+    fn test_gmab_seed_from_grid_respects_budget() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(4)
+            .with_simulation_budget(5);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        assert_eq!(gmab.seed_from_grid(&[10, 10]), 5);
+        assert_eq!(gmab.get_simulations_used(), 5);
+    }
+
+    #[test]
+    fn test_gmab_compare_dominating_configuration() {
+        // with the same seed both start from the same population, only config_a may improve it
+        let config_a = GmabConfig::new(vec![(0, 1000), (0, 1000)])
+            .with_population_size(10)
+            .with_simulation_budget(2000);
+        let config_b = config_a.clone().with_simulation_budget(0);
+        let function = |x: &[i32]| ((x[0] - 500).pow(2) + (x[1] - 500).pow(2)) as f64;
+
+        let result = Gmab::compare(config_a, config_b, function, &[1, 2, 3, 4, 5]);
+        assert_eq!(result.wins_a, 5);
+        assert_eq!(result.wins_b, 0);
+        assert_eq!(result.ties, 0);
+        assert!(result.mean_difference < 0.0);
+        assert!(result.t_statistic < 0.0);
+    }
+
+    #[test]
+    fn test_comparison_result_statistics() {
+        let result = ComparisonResult::from_differences(&[-1.0, -3.0, 0.0, 2.0]);
+        assert_eq!(result.wins_a, 2);
+        assert_eq!(result.wins_b, 1);
+        assert_eq!(result.ties, 1);
+        assert_eq!(result.mean_difference, -0.5);
+        assert!((result.t_statistic - (-0.5 / ((13.0f64 / 3.0).sqrt() / 2.0))).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gmab_remaining_budget() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)]).with_simulation_budget(50);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        assert_eq!(gmab.remaining_budget(), 50);
+
+        gmab.seed_from_grid(&[2, 10]);
+        assert_eq!(gmab.remaining_budget(), 30);
+
+        gmab.simulations_used = 70;
+        assert_eq!(gmab.remaining_budget(), 0);
+    }
+
+    #[test]
+    fn test_gmab_freeze_dimension() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_mutation_rate(0.5)
+            .with_seed(14);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 101 + x[1]) as f64, config);
+        let initial_arms = gmab.arm_memory.len();
+
+        gmab.freeze_dimension(1, 7);
+        for _ in 0..10 {
+            gmab.step();
+        }
+        assert!(gmab.arm_memory.len() > initial_arms);
+        for arm in &gmab.arm_memory[initial_arms..] {
+            assert_eq!(arm.get_action_vector()[1], 7);
+        }
+
+        gmab.unfreeze_dimension(1);
+        let frozen_arms = gmab.arm_memory.len();
+        for _ in 0..10 {
+            gmab.step();
+        }
+        assert!(gmab.arm_memory[frozen_arms..]
+            .iter()
+            .any(|arm| arm.get_action_vector()[1] != 7));
+    }
+
+    #[test]
+    fn test_gmab_restart_around_best() {
+        let config = GmabConfig::new(vec![(0, 1000), (0, 1000)])
+            .with_population_size(11)
+            .with_restart(RestartMode::AroundBest { spread: 0.01 }, 100)
+            .with_seed(15);
+        let mut gmab = Gmab::with_config(
+            |x: &[i32]| ((x[0] - 500).pow(2) + (x[1] - 500).pow(2)) as f64,
+            config,
+        );
+        let best_arm = gmab.get_individuals()[0].clone();
+
+        gmab.restart();
+        let population = gmab.get_individuals();
+        assert_eq!(population.len(), 11);
+        assert!(population.contains(&best_arm));
+
+        let near_best = population
+            .iter()
+            .filter(|arm| {
+                arm.get_action_vector()
+                    .iter()
+                    .zip(best_arm.get_action_vector())
+                    .all(|(gene, best_gene)| (gene - best_gene).abs() <= 10)
+            })
+            .count();
+        // the best arm itself and five arms around it
+        assert!(near_best >= 6);
+        assert_eq!(gmab.get_simulations_used(), 10);
+    }
+
+    #[test]
+    fn test_gmab_restart_on_stagnation() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(6)
+            .with_restart(RestartMode::Random, 2);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        // a constant function never improves
+        gmab.step();
+        gmab.step();
+        assert_eq!(gmab.get_stagnant_generations(), 1);
+        gmab.step();
+        assert_eq!(gmab.get_stagnant_generations(), 0);
+        assert_eq!(gmab.injected_indexes.len(), 5);
+    }
+
+    #[test]
+    fn test_gmab_reseed_matches_fresh_generator() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(6)
+            .with_simulation_budget(1000);
+        let mut first = Gmab::with_config(mock_opti_function, config.clone().with_seed(1));
+        let mut second = Gmab::with_config(mock_opti_function, config.with_seed(2));
+        first.step();
+        second.step();
+        second.step();
+
+        first.reseed(42);
+        second.reseed(42);
+        let mut fresh = StdRng::seed_from_u64(42);
+        let draws: Vec<u64> = (0..5).map(|_| fresh.gen()).collect();
+        let first_draws: Vec<u64> = (0..5).map(|_| first.genetic_algorithm.rng.gen()).collect();
+        assert_eq!(first_draws, draws);
+
+        // identical generators produce identical offspring from the same parents
+        second.reseed(42);
+        first.reseed(42);
+        let parents = first.get_individuals();
+        assert_eq!(
+            first.genetic_algorithm.mutate(&parents),
+            second.genetic_algorithm.mutate(&parents)
+        );
+    }
+
+    #[test]
+    fn test_gmab_dimension_sensitivity() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10), (0, 10)])
+            .with_population_size(10)
+            .with_seed(12);
+        let mut gmab = Gmab::with_config(|x: &[i32]| ((x[1] - 5) * (x[1] - 5)) as f64, config);
+        gmab.optimize(300);
+
+        let sensitivity = gmab.dimension_sensitivity();
+        assert_eq!(sensitivity.len(), 3);
+        assert!(sensitivity
+            .iter()
+            .all(|&s| (0.0..=1.0 + 1e-12).contains(&s)));
+        assert!(sensitivity[1] > sensitivity[0]);
+        assert!(sensitivity[1] > sensitivity[2]);
+        // the fitness is a function of the second gene alone
+        assert!((sensitivity[1] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_gmab_run_stops_on_first_criterion() {
+        use crate::stopping::{Any, MaxSimulations, TargetFitness};
+
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_seed(16);
+        let function = |x: &[i32]| ((x[0] - 50).pow(2) + (x[1] - 50).pow(2)) as f64;
+
+        // the target is out of reach, the budget criterion stops the run
+        let mut gmab = Gmab::with_config(function, config.clone());
+        gmab.run(&mut Any(vec![
+            Box::new(MaxSimulations(300)),
+            Box::new(TargetFitness(-1.0)),
+        ]));
+        assert!(gmab.get_simulations_used() >= 300);
+        assert!(gmab.get_simulations_used() < 400);
+
+        // a lenient target is reached long before the budget
+        let mut gmab = Gmab::with_config(function, config);
+        let initial_best = gmab.best_mean_reward();
+        gmab.run(&mut Any(vec![
+            Box::new(MaxSimulations(5000)),
+            Box::new(TargetFitness(initial_best)),
+        ]));
+        assert_eq!(gmab.get_simulations_used(), 0);
+    }
+
+    #[test]
+    fn test_gmab_extend_budget_continues_run() {
+        use crate::stopping::MaxGenerations;
+
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(100)
+            .with_seed(5);
+        let mut gmab = Gmab::with_config(
+            |x: &[i32]| ((x[0] - 50).pow(2) + (x[1] - 50).pow(2)) as f64,
+            config,
+        );
+
+        gmab.run(&mut MaxGenerations(1000));
+        assert!(gmab.budget_exhausted());
+        let generations = gmab.generations_elapsed;
+        let best_fitness = gmab.best_mean_reward();
+        gmab.step();
+        assert_eq!(gmab.generations_elapsed, generations);
+
+        gmab.extend_budget(200);
+        gmab.run(&mut MaxGenerations(1000));
+        assert!(gmab.generations_elapsed > generations);
+        assert!(gmab.get_simulations_used() >= 300);
+        assert!(gmab.best_mean_reward() <= best_fitness);
+    }
+
+    #[test]
+    fn test_gmab_get_best_k_distinct_skips_poorly_sampled_arms() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(4)
+            .with_min_recommendation_pulls(3);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        // a lucky arm with a single draw and a solid arm pulled several times
+        let mut lucky_arm = Arm::new(&[20, 20]);
+        lucky_arm.record_reward(-10.0);
+        let mut solid_arm = Arm::new(&[30, 30]);
+        for reward in [-2.0, -1.0, -3.0] {
+            solid_arm.record_reward(reward);
+        }
+        for arm in [lucky_arm, solid_arm] {
+            gmab.arm_memory.push(arm.clone());
+            let arm_index = gmab.arm_memory.len() as i32 - 1;
+            gmab.lookup_table
+                .insert(arm.get_action_vector().to_vec(), arm_index);
+            gmab.sample_average_tree
+                .insert(FloatKey::new(arm.get_mean_reward()), arm_index);
+        }
+
+        let best = gmab.get_best_k_distinct(2);
+        assert_eq!(best.len(), 1);
+        assert_eq!(best[0].0.get_action_vector(), &[30, 30]);
+        assert_eq!(best[0].1, 2.0 / 3.0);
+        // standard error from the sample variance of 1
+        assert!((best[0].2 - 1.96 * (1.0f64 / 3.0).sqrt()).abs() < 1e-3);
+        let (lower, upper) = best[0].0.get_confidence_interval(0.95);
+        assert_eq!(best[0].2, (upper - lower) / 2.0);
+    }
+
+    #[test]
+    fn test_gmab_allow_duplicates_pulls_repeated_arms() {
+        // a two point search space and a population of six
+        let config = GmabConfig::new(vec![(0, 1), (0, 0)])
+            .with_population_size(6)
+            .with_allow_duplicates(true)
+            .with_seed(3);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        for _ in 0..3 {
+            let pulls_before: i32 = gmab.arm_memory.iter().map(Arm::get_num_pulls).sum();
+            let simulations_before = gmab.simulations_used;
+            gmab.step();
+
+            // duplicates are merged into the two distinct arms
+            let individuals = gmab.get_individuals();
+            assert_eq!(individuals.len(), 2);
+            assert_ne!(individuals[0], individuals[1]);
+            assert_eq!(gmab.arm_memory.len(), 2);
+
+            // every evaluation of a duplicate is a pull of its arm
+            let pulls: i32 = gmab.arm_memory.iter().map(Arm::get_num_pulls).sum();
+            assert_eq!(
+                (pulls - pulls_before) as usize,
+                gmab.simulations_used - simulations_before
+            );
+        }
+        assert!(gmab.arm_memory.iter().all(|arm| arm.get_num_pulls() > 3));
+    }
+
+    #[test]
+    fn test_gmab_shrinks_population_to_search_space() {
+        let config = GmabConfig::new(vec![(0, 2), (5, 5)])
+            .with_population_size(10)
+            .with_shrink_to_search_space(true);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        assert_eq!(gmab.get_population_size(), 3);
+        assert_eq!(gmab.population_shrunk_from(), Some(10));
+        assert_eq!(gmab.get_individuals().len(), 3);
+
+        gmab.optimize(20);
+        assert_eq!(gmab.get_individuals().len(), 3);
+
+        let config = GmabConfig::new(vec![(0, 20), (5, 5)])
+            .with_population_size(10)
+            .with_shrink_to_search_space(true);
+        let gmab = Gmab::with_config(mock_opti_function, config);
+        assert_eq!(gmab.get_population_size(), 10);
+        assert_eq!(gmab.population_shrunk_from(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_evaluation_timing() {
+        fn slow_opti_function(vec: &[i32]) -> f64 {
+            std::thread::sleep(Duration::from_millis(2));
+            vec.iter().sum::<i32>() as f64
+        }
+
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(4)
+            .with_evaluation_timing(true);
+        let mut gmab = Gmab::with_config(slow_opti_function, config);
+        gmab.optimize(10);
+
+        let timing = gmab.evaluation_timing();
+        assert_eq!(timing.evaluations, gmab.get_simulations_used());
+        assert!(timing.total >= Duration::from_millis(2) * timing.evaluations as u32);
+        assert!(timing.max >= Duration::from_millis(2));
+        assert!(timing.mean() <= timing.max);
+    }
+
+    #[test]
+    fn test_gmab_objective_offset_with_negative_fitness() {
+        fn negative_opti_function(vec: &[i32]) -> f64 {
+            -100.0 - vec.iter().sum::<i32>() as f64
+        }
+
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(6)
+            .with_objective(Objective::Maximize)
+            .with_parent_selection(ParentSelection::Roulette)
+            .with_objective_offset(ObjectiveOffset::Auto);
+        let mut gmab = Gmab::with_config(negative_opti_function, config);
+        gmab.optimize(60);
+
+        let population = gmab.get_individuals();
+        let fitnesses: Vec<f64> = population.iter().map(|arm| gmab.mean_cost(arm)).collect();
+        assert!(gmab.selection_reference().unwrap() >= 100.0);
+
+        gmab.genetic_algorithm
+            .set_selection_reference(gmab.selection_reference());
+        let probabilities = gmab
+            .genetic_algorithm
+            .selection_probabilities(&population, &fitnesses);
+        assert!(probabilities.iter().all(|&probability| probability >= 0.0));
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gmab_surrogate_reduces_evaluations() {
+        struct SumSurrogate;
+
+        impl Surrogate for SumSurrogate {
+            fn predict(&self, action_vector: &[i32]) -> f64 {
+                action_vector.iter().sum::<i32>() as f64
+            }
+
+            fn update(&mut self, _action_vector: &[i32], _fitness: f64) {}
+        }
+
+        fn sum_opti_function(vec: &[i32]) -> f64 {
+            vec.iter().sum::<i32>() as f64
+        }
+
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_mutation_rate(1.0)
+            .with_seed(3);
+
+        let mut plain = Gmab::with_config(sum_opti_function, config.clone());
+        plain.step();
+
+        let mut screened = Gmab::with_config(sum_opti_function, config);
+        screened.set_surrogate(SumSurrogate, 2);
+        screened.step();
+
+        // two offspring plus the re-evaluated population
+        assert_eq!(screened.get_simulations_used(), 12);
+        assert!(screened.get_simulations_used() < plain.get_simulations_used());
+    }
+
+    #[test]
+    fn test_gmab_best_arm_confidence_interval() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(4);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        gmab.optimize(40);
+
+        let best_arm = gmab.get_best_arm();
+        let (lower, upper) = gmab.best_arm_confidence_interval(0.9);
+        assert_eq!((lower, upper), best_arm.get_confidence_interval(0.9));
+        assert!(lower <= best_arm.get_mean_reward() && best_arm.get_mean_reward() <= upper);
+    }
+
+    #[test]
+    fn test_gmab_effective_mutation_sigma() {
+        let config = GmabConfig::new(vec![(0, 10), (-50, 50), (3, 3)]).with_mutation_span(0.2);
+        let gmab = Gmab::with_config(mock_opti_function, config);
+
+        assert_eq!(gmab.effective_mutation_sigma(), vec![2.0, 20.0, 0.0]);
+    }
+
+    #[test]
+    fn test_gmab_absolute_mutation_sigma() {
+        let population = vec![Arm::new(&[50, 50]), Arm::new(&[60, 40])];
+        let mutate = |upper: i32| {
+            let config = GmabConfig::new(vec![(0, upper), (0, upper)]).with_mutation_rate(1.0);
+            let mut gmab = Gmab::with_config(mock_opti_function, config);
+            gmab.set_absolute_mutation_sigma(vec![2.0, 3.0]);
+            assert_eq!(gmab.effective_mutation_sigma(), vec![2.0, 3.0]);
+            gmab.reseed(5);
+            gmab.genetic_algorithm.mutate(&population)
+        };
+
+        // the same draws move the genes equally far whatever the width of the bounds
+        let narrow = mutate(100);
+        assert_eq!(narrow, mutate(100_000));
+        assert!(narrow
+            .iter()
+            .zip(&population)
+            .all(|(mutated, original)| mutated
+                .get_action_vector()
+                .iter()
+                .zip(original.get_action_vector())
+                .all(|(a, b)| (a - b).abs() <= 15)));
+    }
+
+    #[test]
+    fn test_gmab_lexicographic_objectives() {
+        use crate::lexicographic::Lexicographic;
+
+        // the primary objective only cares about the first gene, the secondary one
+        // decides among the arms it ties
+        let objectives = Lexicographic::new(
+            |x: &[i32]| vec![(x[0] - 5).abs() as f64, x[1] as f64],
+            vec![1.0, 1.0],
+        );
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(10)
+            .with_seed(5);
+        let mut gmab = Gmab::with_config(objectives, config);
+
+        assert_eq!(gmab.optimize(1000), vec![5, 0]);
+    }
+
+    #[test]
+    fn test_gmab_odd_population_with_average_crossover() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(5)
+            .with_odd_crossover(OddCrossover::AverageWithBest)
+            .with_seed(2);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        for _ in 0..5 {
+            gmab.step();
+            assert_eq!(gmab.get_individuals().len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_gmab_replay_reproduces_populations() {
+        use crate::trace::replay;
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        // a noisy function whose draws differ between runs
+        let state = AtomicU64::new(12345);
+        let noisy_opti_function = move |x: &[i32]| {
+            let draw = derive_seed(state.fetch_add(1, Ordering::Relaxed), 0);
+            (x[0] + x[1]) as f64 + (draw % 1000) as f64 / 100.0
+        };
+
+        let config = GmabConfig::new(vec![(0, 20), (0, 20)])
+            .with_population_size(6)
+            .with_simulation_budget(200)
+            .with_record_trace(true);
+        let mut gmab = Gmab::with_config(noisy_opti_function, config);
+        let mut populations = vec![gmab.get_individuals()];
+        for _ in 0..5 {
+            gmab.step();
+            populations.push(gmab.get_individuals());
+        }
+
+        let text = gmab.run_trace().unwrap().to_string();
+        assert_eq!(
+            RunTrace::from_text(&text, gmab.config.clone().with_population_size(8)).unwrap_err(),
+            GaError::TraceConfigMismatch
+        );
+        let trace = RunTrace::from_text(&text, gmab.config.clone()).unwrap();
+        let mut replayed = replay(&trace, |_x: &[i32]| panic!("all rewards are recorded"));
+        assert_eq!(replayed.get_individuals(), populations[0]);
+        for population in &populations[1..] {
+            replayed.step();
+            assert_eq!(&replayed.get_individuals(), population);
+        }
+    }
+
+    #[test]
+    fn test_gmab_progress_fraction() {
+        use crate::stopping::MaxGenerations;
+
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(100);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        assert_eq!(gmab.progress_fraction(), 0.0);
+
+        while gmab.get_simulations_used() < 100 {
+            gmab.step();
+            assert_eq!(
+                gmab.progress_fraction(),
+                gmab.get_simulations_used() as f64 / 100.0
+            );
+        }
+        assert_eq!(gmab.progress_fraction(), 1.0);
+
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(1000);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        gmab.step();
+        assert!(gmab.progress_fraction() < 0.25);
+        assert_eq!(gmab.progress_fraction_with(&MaxGenerations(4)), 0.25);
+    }
+
+    #[test]
+    fn test_gmab_sample_initial_landscape() {
+        // the population covers the whole 2 x 2 search space
+        let config = GmabConfig::new(vec![(0, 1), (0, 1)]).with_population_size(4);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + 2 * x[1]) as f64, config);
+
+        assert_eq!(gmab.sample_initial_landscape(), vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(gmab.get_simulations_used(), 4);
+    }
+
+    #[test]
+    fn test_gmab_estimate_ruggedness() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(4)
+            .with_seed(9);
+
+        let mut smooth = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config.clone());
+        let ruggedness = smooth.estimate_ruggedness(300);
+        assert_eq!(smooth.get_simulations_used(), 300);
+        assert!(ruggedness > 0.8);
+
+        // pseudo-random values uncorrelated between neighbouring points
+        let mut rugged = Gmab::with_config(
+            |x: &[i32]| ((x[0] * 7919 + x[1] * 104729) % 1013) as f64,
+            config.clone(),
+        );
+        assert!(rugged.estimate_ruggedness(300).abs() < 0.3);
+
+        let mut flat = Gmab::with_config(mock_opti_function, config.with_simulation_budget(5));
+        assert_eq!(flat.estimate_ruggedness(300), 1.0);
+        assert_eq!(flat.get_simulations_used(), 5);
+    }
+
+    #[test]
+    fn test_gmab_swapping_initializers() {
+        use crate::initializer::{LatinHypercube, Seeded, UniformRandom};
+
+        fn check(config: GmabConfig) -> Vec<Arm> {
+            let gmab = Gmab::with_config(mock_opti_function, config.with_population_size(10));
+            let population = gmab.get_individuals();
+            assert_eq!(population.len(), 10);
+            for (i, arm) in population.iter().enumerate() {
+                assert!(arm
+                    .get_action_vector()
+                    .iter()
+                    .all(|&x| (0..=99).contains(&x)));
+                assert!(!population[i + 1..].contains(arm));
+            }
+            population
+        }
+
+        let config = GmabConfig::new(vec![(0, 99), (0, 99)]).with_seed(4);
+        check(config.clone().with_initializer(UniformRandom));
+
+        // one individual in each tenth of every gene range
+        let population = check(config.clone().with_initializer(LatinHypercube));
+        for j in 0..2 {
+            let mut strata: Vec<i32> = population
+                .iter()
+                .map(|arm| arm.get_action_vector()[j] / 10)
+                .collect();
+            strata.sort();
+            assert_eq!(strata, (0..10).collect::<Vec<i32>>());
+        }
+
+        let seeds = vec![vec![1, 2], vec![3, 4], vec![1, 2]];
+        let population = check(config.with_initializer(Seeded(seeds)));
+        assert!(population.contains(&Arm::new(&[1, 2])));
+        assert!(population.contains(&Arm::new(&[3, 4])));
+    }
+
+    #[test]
+    fn test_gmab_restart_perturbs_hyperparameters() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(9)
+            .with_mutation_rate(0.1)
+            .with_mutation_span(0.1)
+            .with_restart(
+                RestartMode::Hyperparameters {
+                    mutation_rate: (0.3, 0.6),
+                    mutation_span: (0.2, 0.4),
+                    reinit_fraction: 0.5,
+                },
+                1,
+            );
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        // the constant function stagnates after the second generation
+        gmab.step();
+        gmab.step();
+        assert_eq!(gmab.get_stagnant_generations(), 0);
+        assert!((0.3..=0.6).contains(&gmab.get_mutation_rate()));
+        assert!((0.2..=0.4).contains(&gmab.get_mutation_span()));
+        assert_eq!(gmab.injected_indexes.len(), 4);
+    }
+
+    #[test]
+    fn test_gmab_batch_evaluation() {
+        use crate::arm::{BatchOptimizationFn, Batched};
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        struct CountingBatch(Arc<AtomicUsize>);
+
+        impl BatchOptimizationFn for CountingBatch {
+            fn eval_batch(&self, action_vectors: &[Vec<i32>]) -> Vec<f64> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                action_vectors
+                    .iter()
+                    .map(|x| ((x[0] - 30).pow(2) + (x[1] - 60).pow(2)) as f64)
+                    .collect()
+            }
+        }
+
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(1000)
+            .with_seed(8);
+        let batch_calls = Arc::new(AtomicUsize::new(0));
+        let mut batched = Gmab::with_config(
+            Batched(CountingBatch(batch_calls.clone())),
+            config.clone().with_batch_evaluation(true),
+        );
+        let mut per_arm = Gmab::with_config(
+            |x: &[i32]| ((x[0] - 30).pow(2) + (x[1] - 60).pow(2)) as f64,
+            config,
+        );
+
+        // the initial population is one batch as well
+        assert_eq!(batch_calls.load(Ordering::Relaxed), 1);
+        for generation in 1..=5 {
+            batched.step();
+            per_arm.step();
+            assert_eq!(batch_calls.load(Ordering::Relaxed), generation + 1);
+            assert_eq!(batched.get_individuals(), per_arm.get_individuals());
+            assert_eq!(
+                batched.get_simulations_used(),
+                per_arm.get_simulations_used()
+            );
+        }
+    }
+
+    #[test]
+    fn test_gmab_batch_evaluation_charges_every_simulation() {
+        use crate::arm::{BatchOptimizationFn, Batched};
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        struct CountingBatch(Arc<AtomicUsize>);
+
+        impl BatchOptimizationFn for CountingBatch {
+            fn eval_batch(&self, action_vectors: &[Vec<i32>]) -> Vec<f64> {
+                assert!(!action_vectors.is_empty());
+                self.0.fetch_add(action_vectors.len(), Ordering::Relaxed);
+                action_vectors
+                    .iter()
+                    .map(|x| (x[0] + x[1]) as f64)
+                    .collect()
+            }
+        }
+
+        for budget in [35, 47, 58, 100] {
+            let simulated = Arc::new(AtomicUsize::new(0));
+            let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+                .with_population_size(10)
+                .with_batch_evaluation(true)
+                .with_evaluation_timing(true)
+                .with_seed(8);
+            let mut gmab = Gmab::with_config(Batched(CountingBatch(simulated.clone())), config);
+            gmab.optimize(budget);
+
+            // the initial population is not charged
+            let charged = simulated.load(Ordering::Relaxed) - 10;
+            assert_eq!(gmab.get_simulations_used(), charged);
+            #[cfg(feature = "std")]
+            assert_eq!(gmab.evaluation_timing().evaluations, charged);
+        }
+    }
+
+    #[test]
+    fn test_gmab_local_search_polishes_best_arm() {
+        let function = |x: &[i32]| ((x[0] - 37).pow(2) + (x[1] - 81).pow(2)) as f64;
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_seed(12);
+        let mut gmab = Gmab::with_config(function, config);
+        gmab.optimize(200);
+
+        let ga_best = gmab.best_mean_reward();
+        gmab.config.simulation_budget = 2000;
+        gmab.local_search();
+        assert!(gmab.best_mean_reward() <= ga_best);
+        // the smooth bowl has no local optima besides the global one
+        assert_eq!(gmab.best_mean_reward(), 0.0);
+    }
+
+    #[test]
+    fn test_gmab_generations_elapsed() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(1000);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        assert_eq!(gmab.get_generations_elapsed(), 0);
+
+        for _ in 0..7 {
+            gmab.step();
+        }
+        assert_eq!(gmab.get_generations_elapsed(), 7);
+        assert_eq!(gmab.summary().generations, 7);
+    }
+
+    #[test]
+    fn test_gmab_inject_candidates() {
+        let function = |x: &[i32]| ((x[0] - 50).pow(2) + (x[1] - 50).pow(2)) as f64;
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(1000)
+            .with_seed(3);
+        let mut gmab = Gmab::with_config(function, config);
+        gmab.step();
+
+        let optimum = Arm::new(&[50, 50]);
+        assert_eq!(
+            gmab.inject_candidates(vec![optimum.clone(), Arm::new(&[0, 0])]),
+            Ok(1)
+        );
+        assert!(gmab.get_individuals().contains(&optimum));
+        assert_eq!(gmab.get_best_arm(), optimum);
+
+        assert!(gmab.inject_candidates(vec![Arm::new(&[101, 0])]).is_err());
+    }
+
+    #[test]
+    fn test_gmab_zoom_bounds_around_best() {
+        let function = |x: &[i32]| ((x[0] - 95).pow(2) + (x[1] - 20).pow(2)) as f64;
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(2000)
+            .with_zoom(0.5, 2)
+            .with_seed(6);
+        let mut gmab = Gmab::with_config(function, config);
+
+        let mut previous_width = 100;
+        for _ in 0..20 {
+            gmab.step();
+            let best = gmab.arm_memory[*gmab.sample_average_tree.iter().next().unwrap().1 as usize]
+                .get_action_vector()
+                .to_vec();
+            for (j, (lower, upper)) in gmab.get_bounds().into_iter().enumerate() {
+                assert!(0 <= lower && lower < upper && upper <= 100);
+                if gmab.get_generations_elapsed().is_multiple_of(2) {
+                    assert!(lower <= best[j] && best[j] <= upper);
+                }
+            }
+            let width = gmab.get_bounds()[0].1 - gmab.get_bounds()[0].0;
+            assert!(width <= previous_width);
+            previous_width = width;
+        }
+        assert_eq!(previous_width, 1);
+    }
+
+    #[test]
+    fn test_gmab_max_simulations_per_generation() {
+        let function = |x: &[i32]| ((x[0] - 50).pow(2) + (x[1] - 50).pow(2)) as f64;
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(300)
+            .with_max_simulations_per_generation(14);
+        let mut gmab = Gmab::with_config(function, config);
+
+        while gmab.get_simulations_used() < 300 {
+            let simulations_before = gmab.get_simulations_used();
+            gmab.step();
+            let spent = gmab.get_simulations_used() - simulations_before;
+            assert!(spent > 0 && spent <= 14);
+        }
+        assert!(gmab.get_generations_elapsed() >= 300 / 14);
+    }
+
+    #[test]
+    fn test_gmab_best_is_significant() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        // deterministic noise cycling through -3..=3
+        fn noisy(calls: &AtomicUsize, x: &[i32], scale: f64, amplitude: f64) -> f64 {
+            let noise = (calls.fetch_add(1, Ordering::Relaxed) % 7) as f64 - 3.0;
+            (x[0] * 2 + x[1]) as f64 * scale + noise * amplitude
+        }
+        let config = || {
+            GmabConfig::new(vec![(0, 9), (0, 1)])
+                .with_population_size(4)
+                .with_simulation_budget(400)
+                .with_seed(3)
+        };
+
+        let calls = AtomicUsize::new(0);
+        let mut separated =
+            Gmab::with_config(move |x: &[i32]| noisy(&calls, x, 100.0, 1.0), config());
+        separated.optimize(400);
+        assert!(separated.best_is_significant(0.95));
+
+        let calls = AtomicUsize::new(0);
+        let mut overlapping =
+            Gmab::with_config(move |x: &[i32]| noisy(&calls, x, 1.0, 100.0), config());
+        overlapping.optimize(400);
+        assert!(!overlapping.best_is_significant(0.95));
+    }
+
+    #[test]
+    fn test_gmab_coverage_fraction() {
+        let function = |x: &[i32]| (x[0] * 5 + x[1]) as f64;
+        let config = GmabConfig::new(vec![(0, 4), (0, 4)])
+            .with_population_size(4)
+            .with_seed(5);
+        let mut gmab = Gmab::with_config(function, config);
+        assert!(gmab.search_space_is_countable());
+
+        let mut coverage = gmab.coverage_fraction();
+        assert!((coverage - 4.0 / 25.0).abs() < 1e-12);
+        for _ in 0..200 {
+            gmab.step();
+            let next_coverage = gmab.coverage_fraction();
+            assert!(next_coverage >= coverage && next_coverage <= 1.0);
+            coverage = next_coverage;
+        }
+        assert!(coverage > 0.5);
+
+        let huge = Gmab::new(function, vec![(-1_000_000, 1_000_000); 4]);
+        assert!(!huge.search_space_is_countable());
+        assert_eq!(huge.coverage_fraction(), 1.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_catch_panics() {
+        let function = |x: &[i32]| {
+            if x == [3, 3] {
+                panic!("simulator bug");
+            }
+            (x[0] + x[1]) as f64
+        };
+        let config = GmabConfig::new(vec![(0, 4), (0, 4)])
+            .with_population_size(4)
+            .with_initializer(crate::initializer::Seeded(vec![vec![3, 3]]))
+            .with_catch_panics(true)
+            .with_seed(2);
+        let mut gmab = Gmab::with_config(function, config);
+        assert_eq!(gmab.optimize(100), vec![0, 0]);
+
+        let arm_index = gmab.lookup_table[&vec![3, 3]];
+        assert_eq!(
+            gmab.arm_memory[arm_index as usize].get_mean_reward(),
+            f64::MAX
+        );
+        assert!(gmab.worst_observed_cost < f64::MAX);
+        assert!(!gmab.panicked_inputs().is_empty());
+        assert!(gmab.panicked_inputs().iter().all(|x| x[..] == [3, 3]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_catch_panics_in_batch() {
+        use crate::arm::{BatchOptimizationFn, Batched};
 
-        assert_eq!(iter.next(), Some((&FloatKey::new(1.0), &1)));
-        assert_eq!(iter.next(), Some((&FloatKey::new(1.0), &2)));
-        assert_eq!(iter.next(), Some((&FloatKey::new(2.0), &3)));
-        assert_eq!(iter.next(), None);
+        struct Simulator;
+        impl BatchOptimizationFn for Simulator {
+            fn eval_batch(&self, action_vectors: &[Vec<i32>]) -> Vec<f64> {
+                action_vectors
+                    .iter()
+                    .map(|x| {
+                        if x[..] == [3, 3] {
+                            panic!("simulator bug");
+                        }
+                        (x[0] + x[1]) as f64
+                    })
+                    .collect()
+            }
+        }
+
+        let config = GmabConfig::new(vec![(0, 4), (0, 4)])
+            .with_population_size(4)
+            .with_initializer(crate::initializer::Seeded(vec![vec![3, 3]]))
+            .with_batch_evaluation(true)
+            .with_catch_panics(true)
+            .with_seed(2);
+        let mut gmab = Gmab::with_config(Batched(Simulator), config);
+        assert_eq!(gmab.optimize(100), vec![0, 0]);
+
+        // only the culprit of the panicking batch gets the worst fitness
+        let arm_index = gmab.lookup_table[&vec![3, 3]];
+        assert_eq!(
+            gmab.arm_memory[arm_index as usize].get_mean_reward(),
+            f64::MAX
+        );
+        assert!(gmab
+            .arm_memory
+            .iter()
+            .filter(|arm| arm.get_action_vector() != [3, 3])
+            .all(|arm| arm.get_mean_reward() < 10.0));
+        assert!(!gmab.panicked_inputs().is_empty());
+        assert!(gmab.panicked_inputs().iter().all(|x| x[..] == [3, 3]));
     }
 
     #[test]
-    fn test_sorted_multi_map_delete() {
-        let mut map = SortedMultiMap::new();
-        map.insert(FloatKey::new(1.0), 1);
-        map.insert(FloatKey::new(1.0), 2);
-        map.insert(FloatKey::new(2.0), 3);
+    fn test_gmab_set_bounds_projects_population() {
+        let function = |x: &[i32]| (x[0] + x[1]) as f64;
+        let config = GmabConfig::new(vec![(0, 20), (0, 20)])
+            .with_population_size(8)
+            .with_seed(4);
+        let mut gmab = Gmab::with_config(function, config);
+        for _ in 0..5 {
+            gmab.step();
+        }
+        let outside = gmab
+            .get_individuals()
+            .iter()
+            .filter(|arm| arm.get_action_vector().iter().any(|&value| value < 5))
+            .count();
+        assert!(outside > 0);
 
-        assert!(map.delete(&FloatKey::new(1.0), &1));
-        assert!(map.delete(&FloatKey::new(2.0), &3));
-        assert!(!map.delete(&FloatKey::new(2.0), &3));
+        assert_eq!(
+            gmab.set_bounds(vec![5, 5], vec![4, 20]),
+            Err(GaError::InvalidBounds {
+                index: 0,
+                lower: 5,
+                upper: 4
+            })
+        );
+        assert!(gmab.set_bounds(vec![5, 5], vec![20]).is_err());
 
-        let mut iter = map.iter();
+        gmab.set_bounds(vec![5, 5], vec![20, 20]).unwrap();
+        assert_eq!(gmab.get_bounds(), vec![(5, 20), (5, 20)]);
+        let individuals = gmab.get_individuals();
+        assert_eq!(individuals.len(), 8);
+        for arm in &individuals {
+            assert!(arm
+                .get_action_vector()
+                .iter()
+                .all(|&value| (5..=20).contains(&value)));
+        }
+        // the best arm was clamped to the corner
+        assert!(individuals
+            .iter()
+            .any(|arm| arm.get_action_vector() == [5, 5]));
 
-        assert_eq!(iter.next(), Some((&FloatKey::new(1.0), &2)));
-        assert_eq!(iter.next(), None);
+        gmab.step();
+        for arm in gmab.get_individuals() {
+            assert!(arm
+                .get_action_vector()
+                .iter()
+                .all(|&value| (5..=20).contains(&value)));
+        }
     }
 
-    fn mock_opti_function(_vec: &[i32]) -> f64 {
-        0.0
+    #[test]
+    fn test_gmab_boltzmann_selection_anneals() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(10)
+            .with_simulation_budget(1000)
+            .with_parent_selection(ParentSelection::Boltzmann {
+                initial_temperature: 10.0,
+                final_temperature: 0.01,
+            });
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+        let population = gmab.get_individuals();
+        let fitnesses: Vec<f64> = (0..10).map(|i| i as f64).collect();
+
+        let probabilities_at = |gmab: &mut Gmab<_>, simulations_used| {
+            gmab.simulations_used = simulations_used;
+            let temperature = gmab.selection_temperature().unwrap();
+            gmab.genetic_algorithm
+                .set_selection_temperature(temperature);
+            gmab.genetic_algorithm
+                .selection_probabilities(&population, &fitnesses)
+        };
+
+        // early generations select almost uniformly
+        let early = probabilities_at(&mut gmab, 0);
+        assert!(early
+            .iter()
+            .all(|&probability| (probability - 0.1).abs() < 0.01));
+
+        // late generations select greedily
+        let late = probabilities_at(&mut gmab, 1000);
+        assert!(late[0] > 0.99);
     }
 
     #[test]
-    fn test_gmab_new() {
-        let gmab = Gmab::new_with_parameter(
-            mock_opti_function,
-            10,
-            0.1,
-            0.9,
-            0.5,
-            2,
-            vec![0, 0],
-            vec![10, 10],
+    fn test_gmab_protected_arm_survives_unchanged() {
+        let function = |x: &[i32]| ((x[0] - 2).pow(2) + (x[1] - 2).pow(2)) as f64;
+        let config = GmabConfig::new(vec![(0, 20), (0, 20)])
+            .with_population_size(6)
+            .with_seed(8);
+        let mut gmab = Gmab::with_config(function, config);
+        let baseline = Arm::new(&[20, 20]);
+        gmab.protect_arm(baseline.clone()).unwrap();
+        assert!(gmab.protect_arm(Arm::new(&[21, 0])).is_err());
+        gmab.protect_arm(Arm::new(&[0, 0])).unwrap();
+        gmab.protect_arm(Arm::new(&[0, 1])).unwrap();
+        gmab.protect_arm(Arm::new(&[0, 2])).unwrap();
+        let simulations_used = gmab.simulations_used;
+        assert_eq!(
+            gmab.protect_arm(Arm::new(&[0, 3])),
+            Err(GaError::TooManyProtectedArms { population_size: 6 })
         );
-        assert_eq!(gmab.genetic_algorithm.population_size, 10);
-        assert_eq!(gmab.arm_memory.len(), 10);
-        assert_eq!(gmab.lookup_table.len(), 10);
+        // rejected arms are not evaluated
+        assert_eq!(gmab.simulations_used, simulations_used);
+        gmab.protect_arm(baseline.clone()).unwrap();
 
-        // check if there are 10  elements in sample_average_tree
-        let mut count = 0;
-        for _ in gmab.sample_average_tree.iter() {
-            count += 1;
+        for _ in 0..10 {
+            gmab.step();
+            let individuals = gmab.get_individuals();
+            assert_eq!(individuals.len(), 6);
+            assert!(individuals.contains(&baseline));
         }
-        assert_eq!(count, 10);
+        // far from elite, the baseline is still in place
+        assert!(gmab
+            .get_best_k_distinct(6)
+            .iter()
+            .all(|(arm, _, _)| *arm != baseline));
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_gmab_get_arm_index_with_existing() {
-        let mut gmab = Gmab::new_with_parameter(
-            mock_opti_function,
-            10,
-            0.1,
-            0.9,
-            0.5,
-            2,
-            vec![0, 0],
-            vec![10, 10],
+    fn test_gmab_estimate_noise() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        use rand_distr::{Distribution, Normal};
+        use std::sync::Mutex;
+
+        let rng = Mutex::new(StdRng::seed_from_u64(11));
+        let noise = Normal::new(0.0, 2.0).unwrap();
+        let function =
+            move |x: &[i32]| (x[0] + x[1]) as f64 + noise.sample(&mut *rng.lock().unwrap());
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(4)
+            .with_seed(1);
+        let mut gmab = Gmab::with_config(function, config);
+
+        let sigma = gmab.estimate_noise(300);
+        assert!((sigma - 2.0).abs() < 0.15, "estimated sigma {}", sigma);
+        assert_eq!(gmab.get_simulations_used(), 900);
+
+        // deterministic objectives have no noise
+        let mut gmab = Gmab::new(|x: &[i32]| x[0] as f64, vec![(0, 10), (0, 10)]);
+        assert_eq!(gmab.estimate_noise(5), 0.0);
+    }
+
+    #[test]
+    fn test_gmab_common_random_numbers() {
+        use crate::arm::{Crn, CrnOptimizationFn};
+
+        struct Noisy;
+
+        impl CrnOptimizationFn for Noisy {
+            fn eval(&self, action_vector: &[i32], seed: u64) -> f64 {
+                (action_vector[0] + action_vector[1]) as f64 + (seed % 1000) as f64
+            }
+        }
+
+        // the paired difference of two arms is the same in every replication
+        let crn = Crn::new(Noisy, 7);
+        for replication in 0..10 {
+            let difference = crn.evaluate_replication(&[5, 5], replication)
+                - crn.evaluate_replication(&[2, 1], replication);
+            assert_eq!(difference, 7.0);
+        }
+        assert_eq!(
+            Crn::new(Noisy, 7).evaluate_replication(&[3, 3], 4),
+            crn.evaluate_replication(&[3, 3], 4)
         );
-        let arm = Arm::new(&vec![1, 2]);
-        gmab.arm_memory.push(arm.clone());
-        gmab.lookup_table
-            .insert(arm.get_action_vector().to_vec(), 0);
-        assert_eq!(gmab.get_arm_index(&arm), 0);
+
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(6)
+            .with_seed(2);
+        let mut gmab = Gmab::with_config(Crn::new(Noisy, 7), config);
+        gmab.optimize(300);
+
+        // arms with the same number of pulls saw the same noise
+        let arms = gmab.get_best_k_distinct(gmab.arm_memory.len());
+        let mut compared = 0;
+        for (a, _, _) in &arms {
+            for (b, _, _) in &arms {
+                if a.get_num_pulls() == b.get_num_pulls() && a.get_num_pulls() > 1 {
+                    let structural = |arm: &Arm| arm.get_action_vector().iter().sum::<i32>() as f64;
+                    let difference = a.get_mean_reward() - b.get_mean_reward();
+                    assert!((difference - (structural(a) - structural(b))).abs() < 1e-9);
+                    compared += 1;
+                }
+            }
+        }
+        assert!(compared > 0);
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_gmab_max_number_pulls() {
-        let gmab = Gmab::new_with_parameter(
-            mock_opti_function,
-            10,
-            0.1,
-            0.9,
-            0.5,
-            2,
-            vec![0, 0],
-            vec![10, 10],
+    fn test_gmab_write_history_jsonl() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(4)
+            .with_record_history(true);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] - x[1]) as f64 * 0.5, config);
+        gmab.optimize(40);
+        let history = gmab.get_evaluation_history();
+        assert_eq!(history.len(), 4 + gmab.get_simulations_used());
+
+        let mut buffer = Vec::new();
+        gmab.write_history_jsonl(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+
+        let field = |line: &str, name: &str| -> String {
+            let start = line.find(&format!("\"{}\":", name)).unwrap() + name.len() + 3;
+            let rest = &line[start..];
+            let end = match rest.strip_prefix('[') {
+                Some(values) => values.find(']').unwrap() + 2,
+                None => rest.find([',', '}']).unwrap(),
+            };
+            rest[..end].to_string()
+        };
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), history.len());
+        for (line, record) in lines.iter().zip(history) {
+            assert!(line.starts_with('{') && line.ends_with('}'));
+            let action: Vec<i32> = field(line, "action")
+                .trim_matches(|c| c == '[' || c == ']')
+                .split(',')
+                .map(|value| value.parse().unwrap())
+                .collect();
+            assert_eq!(action, record.action_vector);
+            assert_eq!(
+                field(line, "fitness").parse::<f64>().unwrap(),
+                record.fitness
+            );
+            assert_eq!(
+                field(line, "generation").parse::<usize>().unwrap(),
+                record.generation
+            );
+            assert!(field(line, "timestamp").parse::<f64>().unwrap() >= 0.0);
+        }
+        assert!(history.last().unwrap().generation > 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_stream_history_jsonl() {
+        use std::io::Write;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+        impl Write for SharedBuffer {
+            fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(bytes)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let lines = |buffer: &SharedBuffer| {
+            String::from_utf8(buffer.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .count()
+        };
+
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(4);
+        let mut gmab =
+            Gmab::try_with_config_deferred(|x: &[i32]| (x[0] - x[1]) as f64, config).unwrap();
+        let buffer = SharedBuffer::default();
+        gmab.stream_history_jsonl(buffer.clone());
+
+        // records are written as they are evaluated and not kept
+        gmab.step();
+        let written = lines(&buffer);
+        assert_eq!(written, 4 + gmab.get_simulations_used());
+        assert!(gmab.get_evaluation_history().is_empty());
+        gmab.step();
+        assert!(lines(&buffer) > written);
+
+        gmab.finish_history_stream().unwrap();
+        let written = lines(&buffer);
+        gmab.step();
+        assert_eq!(lines(&buffer), written);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_cache_ttl_expires_entries() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(10)
+            .with_cache_ttl(Duration::from_millis(50));
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+
+        let arm = Arm::new(&[11, 12]);
+        assert_eq!(gmab.fitness_of(&arm), 23.0);
+        assert_eq!(gmab.fitness_of(&arm), 23.0);
+        assert_eq!(gmab.get_simulations_used(), 1);
+
+        std::thread::sleep(Duration::from_millis(80));
+        assert_eq!(gmab.fitness_of(&arm), 23.0);
+        assert_eq!(gmab.get_simulations_used(), 2);
+        // the refreshed entry is served from the cache again
+        assert_eq!(gmab.fitness_of(&arm), 23.0);
+        assert_eq!(gmab.get_simulations_used(), 2);
+    }
+
+    #[test]
+    fn test_gmab_deterministic_evaluates_each_arm_once() {
+        // an expired time-to-live must not cause re-evaluations of a deterministic function
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(6)
+            .with_cache_ttl(Duration::ZERO)
+            .with_deterministic(true);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] * 11 + x[1]) as f64, config);
+        let initial_arms = gmab.arm_memory.len();
+
+        gmab.optimize(60);
+
+        // the initial population is not charged, every later arm costs exactly one simulation
+        assert!(gmab.get_simulations_used() > 0);
+        assert_eq!(
+            gmab.get_simulations_used(),
+            gmab.arm_memory.len() - initial_arms
         );
-        assert_eq!(gmab.max_number_pulls(), 1);
     }
 
     #[test]
-    fn test_gmab_find_best_ucb() {
-        let gmab = Gmab::new_with_parameter(
-            mock_opti_function,
-            10,
-            0.1,
-            0.9,
-            0.5,
-            2,
-            vec![0, 0],
-            vec![10, 10],
+    fn test_gmab_novelty_diversifies_selection() {
+        // four clustered arms and a slightly worse outlier
+        let population = vec![
+            Arm::new(&[0, 0]),
+            Arm::new(&[0, 1]),
+            Arm::new(&[1, 0]),
+            Arm::new(&[1, 1]),
+            Arm::new(&[10, 10]),
+        ];
+        let costs = [0.0, 1.0, 2.0, 3.0, 4.0];
+
+        let mean_pool_diversity = |config: GmabConfig| {
+            let mut gmab = Gmab::with_config(mock_opti_function, config.with_seed(3));
+            let selection_fitnesses = gmab.selection_fitnesses(&population, &costs);
+            let total: f64 = (0..200)
+                .map(|_| {
+                    let pool = gmab
+                        .genetic_algorithm
+                        .select_mating_pool(&population, &selection_fitnesses);
+                    gmab.genetic_algorithm.diversity(&pool)
+                })
+                .sum();
+            total / 200.0
+        };
+
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(5)
+            .with_parent_selection(ParentSelection::Rank);
+        let plain = mean_pool_diversity(config.clone());
+        let novel = mean_pool_diversity(config.with_novelty(1.0, 2));
+        assert!(novel > plain);
+    }
+
+    #[test]
+    fn test_gmab_visit_histogram() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(4);
+        let mut gmab = Gmab::with_config(mock_opti_function, config);
+
+        let histogram = gmab.visit_histogram();
+        assert_eq!(histogram.len(), 4);
+        assert!(histogram.values().all(|&visits| visits == 1));
+
+        let arm = gmab.get_individuals()[0].clone();
+        let arm_index = gmab.get_arm_index(&arm);
+        for _ in 0..3 {
+            gmab.sample_and_update(arm_index, arm.clone());
+        }
+        let new_arm = Arm::new(&[11, 11]);
+        gmab.sample_and_update(gmab.get_arm_index(&new_arm), new_arm.clone());
+
+        let histogram = gmab.visit_histogram();
+        assert_eq!(histogram.len(), 5);
+        assert_eq!(histogram[arm.get_action_vector()], 4);
+        assert_eq!(histogram[new_arm.get_action_vector()], 1);
+        assert_eq!(
+            histogram.values().sum::<u32>() as usize,
+            4 + gmab.get_simulations_used()
         );
-        assert_eq!(gmab.find_best_ucb(100), 0);
     }
 
     #[test]
-    fn test_gmab_find_best_ucb_with_existing() {
-        let mut gmab = Gmab::new_with_parameter(
-            mock_opti_function,
-            10,
-            0.1,
-            0.9,
-            0.5,
-            2,
-            vec![0, 0],
-            vec![10, 10],
+    fn test_gmab_deterministic_crowding_keeps_slots() {
+        let function = |x: &[i32]| match (x[0], x[1]) {
+            (0, 0) => 5.0,
+            (1, 1) => 3.0,
+            (9, 9) => 2.0,
+            (10, 10) => 1.0,
+            _ => (x[0] * 11 + x[1]) as f64,
+        };
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(4)
+            .with_replacement_mode(ReplacementMode::DeterministicCrowding)
+            .with_seed(8);
+        let mut gmab = Gmab::with_config(function, config);
+
+        let arms: Vec<Arm> = [[0, 0], [10, 10], [1, 1], [9, 9]]
+            .iter()
+            .map(|genes| Arm::new(genes))
+            .collect();
+        for arm in &arms {
+            gmab.sample_and_update(gmab.get_arm_index(arm), arm.clone());
+        }
+        // the worse parent is replaced by its neighbour, the better one keeps its slot even
+        // though the other offspring beats the first
+        let survivors = gmab.crowding_survivors(&arms[..2], &arms[2..]);
+        assert_eq!(
+            survivors,
+            vec![gmab.get_arm_index(&arms[1]), gmab.get_arm_index(&arms[2])]
         );
+        // an offspring better by less than the epsilon does not replace its parent
+        gmab.config.fitness_epsilon = 2.5;
+        let survivors = gmab.crowding_survivors(&arms[..2], &arms[2..]);
+        assert_eq!(
+            survivors,
+            vec![gmab.get_arm_index(&arms[1]), gmab.get_arm_index(&arms[0])]
+        );
+        gmab.config.fitness_epsilon = 0.0;
 
-        let arm = Arm::new(&vec![1, 2]);
-        gmab.arm_memory.push(arm.clone());
-        gmab.lookup_table
-            .insert(arm.get_action_vector().to_vec(), 0);
+        let sorted_costs = |gmab: &Gmab<_>| {
+            let mut costs: Vec<f64> = gmab
+                .get_individuals()
+                .iter()
+                .map(|arm| gmab.mean_cost(arm))
+                .collect();
+            costs.sort_by(f64::total_cmp);
+            costs
+        };
+        for _ in 0..20 {
+            let before = sorted_costs(&gmab);
+            gmab.step();
+            let after = sorted_costs(&gmab);
+            assert_eq!(after.len(), before.len());
+            assert!(after.iter().zip(&before).all(|(new, old)| new <= old));
+        }
+    }
 
-        let arm2 = Arm::new(&vec![1, 2]);
-        gmab.arm_memory.push(arm2.clone());
-        gmab.lookup_table
-            .insert(arm2.get_action_vector().to_vec(), 1);
+    #[test]
+    fn test_gmab_initialize_without_evaluation() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
 
-        gmab.sample_and_update(0, arm.clone());
-        gmab.sample_and_update(1, arm2.clone());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&calls);
+        let function = move |x: &[i32]| {
+            counted.fetch_add(1, Ordering::SeqCst);
+            (x[0] + x[1]) as f64
+        };
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(6)
+            .with_seed(4);
 
-        assert_eq!(gmab.find_best_ucb(100), 0);
+        let mut gmab = Gmab::try_with_config_deferred(function.clone(), config.clone()).unwrap();
+        gmab.initialize().unwrap();
+        let individuals = gmab.get_individuals();
+        assert_eq!(individuals.len(), 6);
+        assert!(individuals.iter().all(|arm| arm.get_num_pulls() == 0));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        // the inspected population is the one evaluated by the first step
+        gmab.step();
+        assert!(calls.load(Ordering::SeqCst) > 6);
+        for arm in &individuals {
+            assert!(gmab.arm_memory.contains(arm));
+        }
+
+        // stepping without initialize creates the population on its own
+        let mut deferred = Gmab::try_with_config_deferred(function, config.clone()).unwrap();
+        deferred.step();
+        let mut eager = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+        eager.step();
+        assert_eq!(deferred.get_individuals(), eager.get_individuals());
     }
 
     #[test]
-    fn test_gmab_sample_and_update_with_existing() {
-        let mut gmab = Gmab::new_with_parameter(
-            mock_opti_function,
-            10,
-            0.1,
-            0.9,
-            0.5,
-            2,
-            vec![0, 0],
-            vec![10, 10],
+    fn test_gmab_initialize_after_evaluation() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(6)
+            .with_seed(4);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config.clone());
+        assert_eq!(gmab.initialize(), Err(GaError::AlreadyEvaluated));
+
+        // the population still moves on
+        let before = gmab.get_individuals();
+        gmab.step();
+        gmab.step();
+        assert_ne!(gmab.get_individuals(), before);
+
+        // methods that need the best arm evaluate a deferred population first
+        let mut deferred =
+            Gmab::try_with_config_deferred(|x: &[i32]| (x[0] + x[1]) as f64, config).unwrap();
+        deferred.zoom_bounds(0.5);
+        assert_eq!(deferred.arm_memory.len(), 6);
+        assert_eq!(deferred.initialize(), Err(GaError::AlreadyEvaluated));
+    }
+
+    #[test]
+    #[should_panic(expected = "population has not been evaluated yet")]
+    fn test_gmab_best_arm_of_deferred_population() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(6);
+        let gmab = Gmab::try_with_config_deferred(mock_opti_function, config).unwrap();
+        gmab.get_best_arm();
+    }
+
+    #[test]
+    fn test_gmab_generation_gap_replaces_share_of_population() {
+        let config = GmabConfig::new(vec![(0, 1000), (0, 1000)])
+            .with_population_size(10)
+            .with_generation_gap(0.5)
+            .with_seed(21);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+
+        for _ in 0..10 {
+            let before = gmab.get_individuals();
+            gmab.step();
+            let after = gmab.get_individuals();
+            assert_eq!(after.len(), 10);
+            let new_members = after.iter().filter(|arm| !before.contains(arm)).count();
+            assert!((4..=5).contains(&new_members));
+        }
+    }
+
+    #[test]
+    fn test_gmab_generations_iterator() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(200)
+            .with_seed(2);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+
+        let stats: Vec<GenerationStats> = gmab.generations().collect();
+        assert!(!stats.is_empty());
+        assert_eq!(stats.len(), gmab.generations_elapsed);
+        for (index, generation) in stats.iter().enumerate() {
+            assert_eq!(generation.generation, index + 1);
+        }
+        assert!(stats
+            .windows(2)
+            .all(|pair| pair[1].best_fitness <= pair[0].best_fitness));
+
+        // the last generation reached the budget, none follows
+        assert!(stats.last().unwrap().simulations_used >= 200);
+        assert!(stats[stats.len() - 2].simulations_used < 200);
+        assert_eq!(gmab.generations().count(), 0);
+
+        // breaking out early leaves the remaining budget
+        let mut gmab = Gmab::with_config(
+            |x: &[i32]| (x[0] + x[1]) as f64,
+            GmabConfig::new(vec![(0, 100), (0, 100)]).with_population_size(10),
         );
+        for generation in gmab.generations() {
+            if generation.generation == 3 {
+                break;
+            }
+        }
+        assert_eq!(gmab.generations_elapsed, 3);
+    }
 
-        let arm = Arm::new(&vec![1, 2]);
-        gmab.arm_memory.push(arm.clone());
-        gmab.lookup_table
-            .insert(arm.get_action_vector().to_vec(), 0);
+    #[test]
+    fn test_gmab_operator_improvement_counts() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_mutation_rate(0.0)
+            .with_crossover_rate(1.0)
+            .with_simulation_budget(300)
+            .with_seed(4);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+        assert_eq!(gmab.operator_improvement_counts(), (0, 0));
 
-        gmab.sample_and_update(0, arm.clone());
+        gmab.optimize(300);
+        let (crossover, mutation) = gmab.operator_improvement_counts();
+        assert!(crossover > 0);
+        assert_eq!(mutation, 0);
+    }
 
-        assert_eq!(gmab.arm_memory[0].get_num_pulls(), 2);
-        assert_eq!(gmab.arm_memory[0].get_mean_reward(), 0.0);
+    #[test]
+    fn test_gmab_try_step_past_budget() {
+        let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+            .with_population_size(10)
+            .with_simulation_budget(50)
+            .with_seed(1);
+        let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+
+        let population = gmab.try_step().unwrap();
+        assert_eq!(population.len(), 10);
+        while gmab.try_step().is_ok() {}
+        assert!(gmab.simulations_used >= 50);
+
+        let generations = gmab.generations_elapsed;
         assert_eq!(
-            gmab.lookup_table.get(&arm.get_action_vector().to_vec()),
-            Some(&0)
+            gmab.try_step(),
+            Err(GaError::BudgetExhausted { budget: 50 })
         );
+        assert_eq!(gmab.generations_elapsed, generations);
+    }
+
+    #[test]
+    fn test_gmab_dynamic_tracking_follows_moving_optimum() {
+        use crate::arm::{Dynamic, DynamicOptimizationFn};
+
+        // the optimum moves by 8 along both axes every generation
+        struct Moving;
+        impl DynamicOptimizationFn for Moving {
+            fn evaluate_at(&self, x: &[i32], time: usize) -> f64 {
+                let optimum = 20 + 8 * time as i32;
+                ((x[0] - optimum).abs() + (x[1] - optimum).abs()) as f64
+            }
+        }
+
+        let config = GmabConfig::new(vec![(0, 200), (0, 200)])
+            .with_population_size(20)
+            .with_dynamic_tracking(1, 0.3)
+            .with_seed(3);
+        let mut gmab = Gmab::with_config(Dynamic::new(Moving), config);
+        for _ in 0..15 {
+            gmab.step();
+        }
+        assert_eq!(gmab.genetic_algorithm.opti_function.time(), 15);
+
+        // all rewards were observed at the current time
+        let best = gmab.get_best_arm();
+        let optimum = 20 + 8 * 15;
+        let error = (best.get_action_vector()[0] - optimum).abs()
+            + (best.get_action_vector()[1] - optimum).abs();
+        assert_eq!(best.get_mean_reward(), error as f64);
+        assert!(error < 40, "best arm {:?}", best.get_action_vector());
+        // only arms evaluated since the last shift are remembered
+        assert!(gmab.arm_memory.iter().all(|arm| arm.get_num_pulls() > 0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gmab_dynamic_tracking_until_budget_exhausted() {
+        use crate::arm::{Cached, Dynamic, DynamicOptimizationFn};
+
+        struct Moving;
+        impl DynamicOptimizationFn for Moving {
+            fn evaluate_at(&self, x: &[i32], time: usize) -> f64 {
+                (x[0] - time as i32).abs() as f64
+            }
+        }
+
+        for budget in 45..=120 {
+            let config = GmabConfig::new(vec![(0, 100), (0, 100)])
+                .with_population_size(10)
+                .with_dynamic_tracking(1, 0.3)
+                .with_seed(1);
+            let mut gmab = Gmab::with_config(Cached::new(Dynamic::new(Moving)), config);
+            gmab.optimize(budget);
+            assert_eq!(gmab.remaining_budget(), 0);
+            assert_eq!(gmab.population_indexes().len(), 10);
+        }
+    }
+
+    #[test]
+    fn test_seeded_from_iterator_deduplicates() {
+        use crate::initializer::Seeded;
+
+        let candidates = vec![
+            Arm::new(&[1, 2]),
+            Arm::new(&[3, 4]),
+            Arm::new(&[1, 2]),
+            Arm::new(&[5, 6]),
+            Arm::new(&[3, 4]),
+        ];
+        let seeds: Seeded = candidates.into_iter().collect();
+        assert_eq!(seeds.0, vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+            .with_population_size(4)
+            .with_initializer(seeds);
+        let gmab = Gmab::with_config(mock_opti_function, config);
+        let individuals = gmab.get_individuals();
+        for genes in [[1, 2], [3, 4], [5, 6]] {
+            assert!(individuals.contains(&Arm::new(&genes)));
+        }
+    }
+
+    #[test]
+    fn test_gmab_expected_improvement() {
+        let config = GmabConfig::new(vec![(0, 10), (0, 10)]).with_population_size(4);
+        let gmab = Gmab::with_config(|x: &[i32]| (x[0] + x[1]) as f64, config);
+        // the incumbent has a single pull and therefore no uncertainty
+        let incumbent = gmab.best_mean_reward();
+
+        let candidate = |rewards: [f64; 2]| {
+            let mut arm = Arm::new(&[20, 20]);
+            for reward in rewards {
+                arm.record_reward(reward);
+            }
+            arm
+        };
+        // standard error sqrt(1 / 2)
+        let sigma = 0.5f64.sqrt();
+
+        // equal means: sigma * phi(0)
+        let tied = candidate([incumbent - 1.0, incumbent + 1.0]);
+        let expected = sigma / (2.0 * core::f64::consts::PI).sqrt();
+        assert!((gmab.expected_improvement(&tied) - expected).abs() < 1e-6);
+
+        // one standard error better: sigma * (Phi(1) + phi(1))
+        let better = candidate([incumbent - sigma - 1.0, incumbent - sigma + 1.0]);
+        let expected = sigma * (0.841344746068543 + 0.241970724519143);
+        assert!((gmab.expected_improvement(&better) - expected).abs() < 1e-6);
+
+        // a certain candidate improves by exactly the difference or not at all
+        let mut certain = Arm::new(&[20, 20]);
+        certain.record_reward(incumbent - 2.0);
+        assert_eq!(gmab.expected_improvement(&certain), 2.0);
+        let mut worse = Arm::new(&[20, 20]);
+        worse.record_reward(incumbent + 2.0);
+        assert_eq!(gmab.expected_improvement(&worse), 0.0);
     }
 }
@@ -0,0 +1,77 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use crate::arm::Arm;
+use crate::Set;
+
+// produces the individuals of a new population; values off the grid or outside of the
+// bounds are snapped onto it, duplicates and missing individuals are replaced by uniform
+// random ones
+pub trait PopulationInitializer {
+    fn init(&self, size: usize, bounds: &[(i32, i32)], rng: &mut dyn RngCore) -> Vec<Arm>;
+}
+
+pub struct UniformRandom;
+
+impl PopulationInitializer for UniformRandom {
+    fn init(&self, size: usize, bounds: &[(i32, i32)], rng: &mut dyn RngCore) -> Vec<Arm> {
+        (0..size)
+            .map(|_| {
+                let genes: Vec<i32> = bounds
+                    .iter()
+                    .map(|&(lower, upper)| rng.gen_range(lower..=upper))
+                    .collect();
+                Arm::new(&genes)
+            })
+            .collect()
+    }
+}
+
+// every gene range is split into `size` strata and each stratum is used by exactly one
+// individual
+pub struct LatinHypercube;
+
+impl PopulationInitializer for LatinHypercube {
+    fn init(&self, size: usize, bounds: &[(i32, i32)], rng: &mut dyn RngCore) -> Vec<Arm> {
+        let mut genes = vec![Vec::with_capacity(bounds.len()); size];
+        for &(lower, upper) in bounds {
+            let width = (i64::from(upper) - i64::from(lower) + 1) as f64 / size as f64;
+            let mut strata: Vec<usize> = (0..size).collect();
+            strata.shuffle(rng);
+
+            for (individual, stratum) in genes.iter_mut().zip(strata) {
+                let offset = ((stratum as f64 + rng.gen::<f64>()) * width) as i64;
+                individual.push((i64::from(lower) + offset).min(i64::from(upper)) as i32);
+            }
+        }
+        genes.iter().map(|genes| Arm::new(genes)).collect()
+    }
+}
+
+// starts from known individuals, the rest of the population is random
+pub struct Seeded(pub Vec<Vec<i32>>);
+
+impl PopulationInitializer for Seeded {
+    fn init(&self, size: usize, _bounds: &[(i32, i32)], _rng: &mut dyn RngCore) -> Vec<Arm> {
+        self.0
+            .iter()
+            .take(size)
+            .map(|genes| Arm::new(genes))
+            .collect()
+    }
+}
+
+// collects candidate arms into seeds, repeated action vectors are kept once
+impl FromIterator<Arm> for Seeded {
+    fn from_iter<I: IntoIterator<Item = Arm>>(arms: I) -> Self {
+        let mut seen = Set::new();
+        Seeded(
+            arms.into_iter()
+                .map(|arm| arm.get_action_vector().to_vec())
+                .filter(|genes| seen.insert(genes.clone()))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::cell::RefCell;
+use core::cmp::Ordering;
+#[cfg(not(feature = "std"))]
+use core::panic::RefUnwindSafe;
+
+use crate::arm::OptimizationFn;
+
+pub trait MultiObjectiveFn {
+    fn evaluate(&self, action_vector: &[i32]) -> Vec<f64>;
+}
+
+impl<F: Fn(&[i32]) -> Vec<f64>> MultiObjectiveFn for F {
+    fn evaluate(&self, action_vector: &[i32]) -> Vec<f64> {
+        self(action_vector)
+    }
+}
+
+// distinct objective vectors seen so far, sorted by `compare`, with the cost assigned to each
+type Ranking = Vec<(Vec<f64>, f64)>;
+
+// objectives in decreasing priority, values closer than the objective's epsilon
+// count as equal so that the next objective breaks the tie
+pub struct Lexicographic<G: MultiObjectiveFn> {
+    function: G,
+    epsilons: Vec<f64>,
+    #[cfg(feature = "std")]
+    ranking: std::sync::Mutex<Ranking>,
+    #[cfg(not(feature = "std"))]
+    ranking: RefCell<Ranking>,
+}
+
+// without std a panic cannot be caught, the ranking is never observed half updated
+#[cfg(not(feature = "std"))]
+impl<G: MultiObjectiveFn + RefUnwindSafe> RefUnwindSafe for Lexicographic<G> {}
+
+impl<G: MultiObjectiveFn> Lexicographic<G> {
+    pub fn new(function: G, epsilons: Vec<f64>) -> Self {
+        assert!(
+            !epsilons.is_empty() && epsilons.iter().all(|&epsilon| epsilon > 0.0),
+            "every objective needs a positive epsilon"
+        );
+        Lexicographic {
+            function,
+            epsilons,
+            ranking: Default::default(),
+        }
+    }
+
+    pub fn compare(&self, a: &[f64], b: &[f64]) -> Ordering {
+        for (index, &epsilon) in self.epsilons.iter().enumerate() {
+            if (a[index] - b[index]).abs() < epsilon {
+                continue;
+            }
+            return a[index].total_cmp(&b[index]);
+        }
+        Ordering::Equal
+    }
+
+    // single fitness ordered like `compare` among all objective vectors scalarized so far:
+    // a new vector gets a cost between those of its neighbours in the ranking, the cost of a
+    // vector never changes once assigned
+    pub fn scalarize(&self, objectives: &[f64]) -> f64 {
+        assert_eq!(
+            objectives.len(),
+            self.epsilons.len(),
+            "expected one objective per epsilon"
+        );
+        #[cfg(feature = "std")]
+        let mut ranking = self.ranking.lock().unwrap();
+        #[cfg(not(feature = "std"))]
+        let mut ranking = self.ranking.borrow_mut();
+
+        let position =
+            match ranking.binary_search_by(|(ranked, _cost)| self.compare(ranked, objectives)) {
+                Ok(position) => return ranking[position].1,
+                Err(position) => position,
+            };
+        let cost = match (
+            position.checked_sub(1).map(|previous| ranking[previous].1),
+            ranking.get(position).map(|(_objectives, cost)| *cost),
+        ) {
+            (None, None) => 0.0,
+            (Some(worse), None) => worse + 1.0,
+            (None, Some(better)) => better - 1.0,
+            // after about 50 insertions into the same gap the midpoint equals a neighbour,
+            // the vector then ties with it
+            (Some(below), Some(above)) => below + (above - below) / 2.0,
+        };
+        ranking.insert(position, (objectives.to_vec(), cost));
+        cost
+    }
+}
+
+impl<G: MultiObjectiveFn> OptimizationFn for Lexicographic<G> {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        self.scalarize(&self.function.evaluate(action_vector))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_secondary_objective_breaks_primary_ties() {
+        let lexicographic =
+            Lexicographic::new(|x: &[i32]| vec![x[0] as f64, x[1] as f64], vec![1.0, 1.0]);
+
+        // equal primary, the secondary objective decides
+        assert_eq!(
+            lexicographic.compare(&[2.0, 1.0], &[2.0, 3.0]),
+            Ordering::Less
+        );
+        assert!(lexicographic.evaluate(&[2, 1]) < lexicographic.evaluate(&[2, 3]));
+
+        // a better primary wins regardless of the secondary objective
+        assert_eq!(
+            lexicographic.compare(&[1.0, 100.0], &[2.0, -100.0]),
+            Ordering::Less
+        );
+        assert!(lexicographic.evaluate(&[1, 100]) < lexicographic.evaluate(&[2, -100]));
+
+        // primaries within the same epsilon are tied
+        let coarse =
+            Lexicographic::new(|x: &[i32]| vec![x[0] as f64, x[1] as f64], vec![10.0, 1.0]);
+        assert_eq!(
+            coarse.compare(&[11.0, 5.0], &[14.0, 2.0]),
+            Ordering::Greater
+        );
+        assert!(coarse.evaluate(&[11, 5]) > coarse.evaluate(&[14, 2]));
+
+        // epsilon is a tolerance, not a grid
+        let tolerant = Lexicographic::new(|x: &[i32]| vec![0.0, x[0] as f64], vec![1.0, 1.0]);
+        assert_eq!(
+            tolerant.compare(&[0.999, 5.0], &[1.001, 2.0]),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_large_objectives_keep_breaking_ties() {
+        let lexicographic = Lexicographic::new(
+            |x: &[i32]| vec![1e9 + (x[0] / 2) as f64, 1e6 * x[1] as f64 + x[2] as f64],
+            vec![0.5, 0.5],
+        );
+        let arms = [
+            [4, 3, 2],
+            [4, 3, 1],
+            [5, 3, 0],
+            [2, 9, 9],
+            [3, 0, 0],
+            [4, 3, 3],
+        ];
+        let costs: Vec<f64> = arms.iter().map(|x| lexicographic.evaluate(x)).collect();
+        let objectives: Vec<Vec<f64>> = arms
+            .iter()
+            .map(|x| lexicographic.function.evaluate(x))
+            .collect();
+
+        for a in 0..arms.len() {
+            for b in 0..arms.len() {
+                assert_eq!(
+                    costs[a].total_cmp(&costs[b]),
+                    lexicographic.compare(&objectives[a], &objectives[b]),
+                    "{:?} against {:?}",
+                    arms[a],
+                    arms[b]
+                );
+            }
+        }
+        // the cost of a vector seen before does not change
+        assert_eq!(lexicographic.evaluate(&[4, 3, 2]), costs[0]);
+    }
+}
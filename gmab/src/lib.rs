@@ -1,4 +1,31 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+// float methods come from num_traits::Float without std, except in tests which always link std
+
+extern crate alloc;
+
 pub mod arm;
+pub mod benchmarks;
+pub mod config;
+pub mod error;
 mod genetic;
 pub mod gmab;
+pub mod initializer;
+pub mod lexicographic;
+pub mod prelude;
+mod selection;
 mod sorted_multi_map;
+pub mod stopping;
+pub mod surrogate;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod trace;
+
+// hash based collections need std, BTreeMap/BTreeSet are used in their place otherwise
+#[cfg(feature = "std")]
+pub(crate) type Map<K, V> = std::collections::HashMap<K, V>;
+#[cfg(not(feature = "std"))]
+pub(crate) type Map<K, V> = alloc::collections::BTreeMap<K, V>;
+#[cfg(feature = "std")]
+pub(crate) type Set<T> = std::collections::HashSet<T>;
+#[cfg(not(feature = "std"))]
+pub(crate) type Set<T> = alloc::collections::BTreeSet<T>;
@@ -0,0 +1,40 @@
+//! Common types of the crate, imported with `use gmab::prelude::*;`.
+//!
+//! ```
+//! use gmab::prelude::*;
+//!
+//! let config = GmabConfig::new(vec![(0, 10), (0, 10)])
+//!     .with_population_size(4)
+//!     .with_seed(1);
+//! let mut gmab = Gmab::with_config(|x: &[i32]| (x[0] - 3).pow(2) as f64 + x[1] as f64, config);
+//!
+//! let best = gmab.optimize(200);
+//! assert_eq!(best.len(), 2);
+//! ```
+
+#[cfg(feature = "std")]
+pub use crate::arm::Cached;
+pub use crate::arm::{
+    merge_populations, population_diff, prune_arms, prune_dominated_arms, Arm, BatchOptimizationFn,
+    Batched, Crn, CrnOptimizationFn, Dynamic, DynamicOptimizationFn, OptimizationFn,
+    PopulationDiff,
+};
+pub use crate::benchmarks::{Benchmark, Rastrigin, Rosenbrock, Sphere};
+pub use crate::config::{
+    search_space_size, search_space_size_with_steps, FitnessScaling, GmabConfig,
+    MutationDistribution, Objective, ObjectiveOffset, OddCrossover, Pairing, ParentSelection,
+    ReplacementMode, TieBreak,
+};
+pub use crate::error::GaError;
+pub use crate::gmab::{
+    derive_seed, ComparisonResult, EvaluationRecord, EvaluationTiming, GenerationStats,
+    Generations, Gmab, ReplicationSummary, RunSummary,
+};
+pub use crate::initializer::{LatinHypercube, PopulationInitializer, Seeded, UniformRandom};
+pub use crate::lexicographic::{Lexicographic, MultiObjectiveFn};
+pub use crate::stopping::{
+    All, Any, MaxGenerations, MaxSimulations, MaxStagnation, MaxWallTime, RunState,
+    StoppingCriterion, TargetFitness,
+};
+pub use crate::surrogate::Surrogate;
+pub use crate::trace::{replay, Replay, RunTrace};
@@ -0,0 +1,241 @@
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+use crate::arm::Arm;
+use crate::config::FitnessScaling;
+
+// standard deviations below this (relative to the mean) are treated as no spread at all
+const SIGMA_TOLERANCE: f64 = 1e-9;
+
+// indexes of the population from best (lowest cost) to worst, ties are broken by action vector
+pub(crate) fn rank_order(costs: &[f64], population: &[Arm]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..costs.len()).collect();
+    order.sort_by(|&a, &b| {
+        costs[a].total_cmp(&costs[b]).then_with(|| {
+            population[a]
+                .get_action_vector()
+                .cmp(population[b].get_action_vector())
+        })
+    });
+    order
+}
+
+// turns fitnesses (lower is better) into non-negative selection scores (higher is better)
+// relative to `reference`, by default the worst individual getting a score of zero
+fn selection_scores(fitnesses: &[f64], reference: Option<f64>) -> Vec<f64> {
+    let reference = reference.unwrap_or_else(|| fitnesses.iter().copied().fold(f64::MIN, f64::max));
+    fitnesses
+        .iter()
+        .map(|fitness| (reference - fitness).max(0.0))
+        .collect()
+}
+
+fn scale_scores(scores: &[f64], fitness_scaling: FitnessScaling) -> Vec<f64> {
+    match fitness_scaling {
+        FitnessScaling::Linear => {
+            let score_max = scores.iter().copied().fold(0.0, f64::max);
+            scores
+                .iter()
+                .map(|score| {
+                    if score_max > 0.0 {
+                        1.0 + score / score_max
+                    } else {
+                        1.0
+                    }
+                })
+                .collect()
+        }
+        FitnessScaling::SigmaScaling => {
+            let n = scores.len() as f64;
+            let mean = scores.iter().sum::<f64>() / n;
+            let sigma = (scores
+                .iter()
+                .map(|score| (score - mean).powi(2))
+                .sum::<f64>()
+                / n)
+                .sqrt();
+
+            scores
+                .iter()
+                .map(|score| {
+                    if sigma <= SIGMA_TOLERANCE * (1.0 + mean.abs()) {
+                        1.0
+                    } else {
+                        // individuals far below the mean keep a small chance of being selected
+                        (1.0 + (score - mean) / (2.0 * sigma)).max(0.1)
+                    }
+                })
+                .collect()
+        }
+        FitnessScaling::PowerLaw { exponent } => {
+            scores.iter().map(|score| score.powf(exponent)).collect()
+        }
+    }
+}
+
+fn normalize(weights: Vec<f64>) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 || !total.is_finite() {
+        return vec![1.0 / weights.len() as f64; weights.len()];
+    }
+    weights.iter().map(|weight| weight / total).collect()
+}
+
+pub(crate) fn roulette_probabilities(
+    fitnesses: &[f64],
+    reference: Option<f64>,
+    fitness_scaling: Option<FitnessScaling>,
+) -> Vec<f64> {
+    let scores = selection_scores(fitnesses, reference);
+    match fitness_scaling {
+        Some(fitness_scaling) => normalize(scale_scores(&scores, fitness_scaling)),
+        None => normalize(scores),
+    }
+}
+
+pub(crate) fn rank_probabilities(fitnesses: &[f64], population: &[Arm]) -> Vec<f64> {
+    // the best individual gets weight n, the worst one weight 1
+    let mut weights = vec![0.0; fitnesses.len()];
+    for (rank, index) in rank_order(fitnesses, population).into_iter().enumerate() {
+        weights[index] = (fitnesses.len() - rank) as f64;
+    }
+    normalize(weights)
+}
+
+pub(crate) fn boltzmann_probabilities(fitnesses: &[f64], temperature: f64) -> Vec<f64> {
+    let best = fitnesses.iter().copied().fold(f64::MAX, f64::min);
+    let worst = fitnesses.iter().copied().fold(f64::MIN, f64::max);
+    let range = if worst > best { worst - best } else { 1.0 };
+    let weights = fitnesses
+        .iter()
+        .map(|fitness| (-(fitness - best) / range / temperature).exp())
+        .collect();
+    normalize(weights)
+}
+
+// probability of winning a tournament of `size` uniformly drawn competitors, the best
+// competitor winning
+pub(crate) fn tournament_probabilities(
+    fitnesses: &[f64],
+    population: &[Arm],
+    size: usize,
+    replacement: bool,
+) -> Vec<f64> {
+    let n = fitnesses.len();
+    let mut probabilities = vec![0.0; n];
+    for (rank, index) in rank_order(fitnesses, population).into_iter().enumerate() {
+        // individuals ranked below this one
+        let worse = n - rank - 1;
+        probabilities[index] = if replacement {
+            // all competitors are no better, minus the tournaments without this one
+            let draws = size as i32;
+            (((worse + 1) as f64 / n as f64).powi(draws)) - ((worse as f64 / n as f64).powi(draws))
+        } else {
+            // this one and size - 1 of the worse individuals are drawn
+            let size = size.min(n);
+            (0..size - 1).fold(size as f64 / n as f64, |probability, j| {
+                probability * worse.saturating_sub(j) as f64 / (n - 1 - j) as f64
+            })
+        };
+    }
+    probabilities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roulette_probabilities_prefer_low_fitness() {
+        let fitnesses = [1.0, 3.0, 2.0];
+
+        let probabilities = roulette_probabilities(&fitnesses, None, Some(FitnessScaling::Linear));
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(probabilities[0] > probabilities[2]);
+        assert!(probabilities[2] > probabilities[1]);
+        assert!((probabilities[0] - 2.0 * probabilities[1]).abs() < 1e-12);
+
+        let population = vec![Arm::new(&[0]), Arm::new(&[1]), Arm::new(&[2])];
+        assert_eq!(
+            rank_probabilities(&fitnesses, &population),
+            vec![0.5, 1.0 / 6.0, 1.0 / 3.0]
+        );
+    }
+
+    #[test]
+    fn test_sigma_scaling_tiny_variance_is_uniform() {
+        let fitnesses = [1000.0, 1000.0 + 1e-10, 1000.0 - 1e-10, 1000.0];
+
+        let probabilities =
+            roulette_probabilities(&fitnesses, None, Some(FitnessScaling::SigmaScaling));
+        for probability in probabilities {
+            assert!((probability - 0.25).abs() < 1e-6);
+        }
+
+        // a clear spread still favours the better individuals
+        let probabilities = roulette_probabilities(
+            &[1.0, 2.0, 3.0, 4.0],
+            None,
+            Some(FitnessScaling::SigmaScaling),
+        );
+        assert!(probabilities[0] > probabilities[3]);
+
+        // scores 3, 2, 1, 0 have mean 1.5 and standard deviation sqrt(1.25), the weights
+        // 1 + (score - mean) / (2 sigma) sum up to 4
+        let sigma = 1.25f64.sqrt();
+        for (probability, score) in probabilities.iter().zip([3.0, 2.0, 1.0, 0.0]) {
+            let weight: f64 = 1.0 + (score - 1.5) / (2.0 * sigma);
+            assert!((probability - weight / 4.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_roulette_probabilities_with_reference() {
+        let fitnesses = [-5.0, -3.0, -1.0];
+
+        let probabilities = roulette_probabilities(&fitnesses, Some(0.0), None);
+        assert_eq!(probabilities, vec![5.0 / 9.0, 3.0 / 9.0, 1.0 / 9.0]);
+
+        // individuals worse than the reference are never selected
+        let probabilities = roulette_probabilities(&fitnesses, Some(-2.0), None);
+        assert_eq!(probabilities, vec![0.75, 0.25, 0.0]);
+    }
+
+    #[test]
+    fn test_rank_order_breaks_ties_by_action_vector() {
+        let population = vec![
+            Arm::new(&[3, 1]),
+            Arm::new(&[0, 5]),
+            Arm::new(&[1, 0]),
+            Arm::new(&[0, 2]),
+        ];
+        let costs = [2.0, 1.0, 0.5, 1.0];
+
+        assert_eq!(rank_order(&costs, &population), vec![2, 3, 1, 0]);
+    }
+
+    #[test]
+    fn test_tournament_probabilities() {
+        let fitnesses = [3.0, 1.0, 4.0, 2.0, 5.0];
+        let population: Vec<Arm> = (0..5).map(|i| Arm::new(&[i])).collect();
+
+        for replacement in [true, false] {
+            // a single competitor is a uniformly random pick
+            let probabilities = tournament_probabilities(&fitnesses, &population, 1, replacement);
+            assert!(probabilities.iter().all(|p| (p - 0.2).abs() < 1e-12));
+
+            let probabilities = tournament_probabilities(&fitnesses, &population, 3, replacement);
+            assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            assert!(probabilities[1] > probabilities[3]);
+            assert!(probabilities[3] > probabilities[0]);
+        }
+
+        // large tournaments almost always pick the best
+        let probabilities = tournament_probabilities(&fitnesses, &population, 50, true);
+        assert!(probabilities[1] > 0.99);
+        let probabilities = tournament_probabilities(&fitnesses, &population, 5, false);
+        assert_eq!(probabilities, vec![0.0, 1.0, 0.0, 0.0, 0.0]);
+    }
+}
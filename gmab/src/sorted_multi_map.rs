@@ -1,7 +1,8 @@
-use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub(crate) struct FloatKey(f64);
 
 impl FloatKey {
@@ -15,9 +16,17 @@ impl FloatKey {
 
 impl Eq for FloatKey {}
 
+impl PartialOrd for FloatKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for FloatKey {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).expect("No NaNs allowed, so this will never panic")
+        self.0
+            .partial_cmp(&other.0)
+            .expect("No NaNs allowed, so this will never panic")
     }
 }
 
@@ -34,7 +43,7 @@ impl<K: Ord, V: PartialEq> SortedMultiMap<K, V> {
     }
 
     pub fn insert(&mut self, key: K, value: V) {
-        self.inner.entry(key).or_insert_with(Vec::new).push(value);
+        self.inner.entry(key).or_default().push(value);
     }
 
     pub fn delete(&mut self, key: &K, value: &V) -> bool {
@@ -50,10 +59,9 @@ impl<K: Ord, V: PartialEq> SortedMultiMap<K, V> {
         false
     }
 
-
     pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
         self.inner
             .iter()
             .flat_map(|(key, values)| values.iter().map(move |value| (key, value)))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,203 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::config::Objective;
+
+// snapshot of a run handed to the stopping criteria before every generation
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunState {
+    pub generations: usize,
+    pub simulations_used: usize,
+    pub best_fitness: f64,
+    pub stagnant_generations: usize,
+    pub wall_time: Duration,
+    pub objective: Objective,
+}
+
+pub trait StoppingCriterion {
+    fn should_stop(&mut self, state: &RunState) -> bool;
+
+    // share of the limit reached so far in [0, 1], None for criteria without a limit
+    fn progress(&self, _state: &RunState) -> Option<f64> {
+        None
+    }
+}
+
+impl<C: StoppingCriterion + ?Sized> StoppingCriterion for Box<C> {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        (**self).should_stop(state)
+    }
+
+    fn progress(&self, state: &RunState) -> Option<f64> {
+        (**self).progress(state)
+    }
+}
+
+fn fraction(used: f64, limit: f64) -> f64 {
+    if limit <= 0.0 {
+        return 1.0;
+    }
+    (used / limit).clamp(0.0, 1.0)
+}
+
+pub struct MaxSimulations(pub usize);
+
+impl StoppingCriterion for MaxSimulations {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.simulations_used >= self.0
+    }
+
+    fn progress(&self, state: &RunState) -> Option<f64> {
+        Some(fraction(state.simulations_used as f64, self.0 as f64))
+    }
+}
+
+pub struct MaxGenerations(pub usize);
+
+impl StoppingCriterion for MaxGenerations {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.generations >= self.0
+    }
+
+    fn progress(&self, state: &RunState) -> Option<f64> {
+        Some(fraction(state.generations as f64, self.0 as f64))
+    }
+}
+
+// stops once the best fitness is at least as good as the target
+pub struct TargetFitness(pub f64);
+
+impl StoppingCriterion for TargetFitness {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.objective.cost(state.best_fitness) <= state.objective.cost(self.0)
+    }
+}
+
+pub struct MaxStagnation(pub usize);
+
+impl StoppingCriterion for MaxStagnation {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.stagnant_generations >= self.0
+    }
+}
+
+// wall time is only measured with std, without it this criterion never triggers
+pub struct MaxWallTime(pub Duration);
+
+impl StoppingCriterion for MaxWallTime {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.wall_time >= self.0
+    }
+
+    fn progress(&self, state: &RunState) -> Option<f64> {
+        Some(fraction(
+            state.wall_time.as_secs_f64(),
+            self.0.as_secs_f64(),
+        ))
+    }
+}
+
+// every criterion is consulted on each check so that stateful criteria stay up to date
+pub struct Any(pub Vec<Box<dyn StoppingCriterion + Send + Sync>>);
+
+impl StoppingCriterion for Any {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        let mut stop = false;
+        for criterion in self.0.iter_mut() {
+            stop |= criterion.should_stop(state);
+        }
+        stop
+    }
+
+    // the run ends with the first criterion, so the most advanced one counts
+    fn progress(&self, state: &RunState) -> Option<f64> {
+        self.0
+            .iter()
+            .filter_map(|criterion| criterion.progress(state))
+            .reduce(f64::max)
+    }
+}
+
+pub struct All(pub Vec<Box<dyn StoppingCriterion + Send + Sync>>);
+
+impl StoppingCriterion for All {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        let mut stop = !self.0.is_empty();
+        for criterion in self.0.iter_mut() {
+            stop &= criterion.should_stop(state);
+        }
+        stop
+    }
+
+    fn progress(&self, state: &RunState) -> Option<f64> {
+        self.0
+            .iter()
+            .filter_map(|criterion| criterion.progress(state))
+            .reduce(f64::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn state(simulations_used: usize, best_fitness: f64) -> RunState {
+        RunState {
+            generations: 0,
+            simulations_used,
+            best_fitness,
+            stagnant_generations: 0,
+            wall_time: Duration::ZERO,
+            objective: Objective::Minimize,
+        }
+    }
+
+    #[test]
+    fn test_any_and_all() {
+        let mut any = Any(vec![
+            Box::new(MaxSimulations(100)),
+            Box::new(TargetFitness(1.0)),
+        ]);
+        let mut all = All(vec![
+            Box::new(MaxSimulations(100)),
+            Box::new(TargetFitness(1.0)),
+        ]);
+
+        assert!(!any.should_stop(&state(50, 2.0)));
+        assert!(any.should_stop(&state(150, 2.0)));
+        assert!(any.should_stop(&state(50, 0.5)));
+
+        assert!(!all.should_stop(&state(150, 2.0)));
+        assert!(!all.should_stop(&state(50, 0.5)));
+        assert!(all.should_stop(&state(150, 0.5)));
+    }
+
+    #[test]
+    fn test_target_fitness_respects_objective() {
+        let mut target = TargetFitness(10.0);
+        let mut reached = state(0, 12.0);
+
+        assert!(!target.should_stop(&reached));
+        reached.objective = Objective::Maximize;
+        assert!(target.should_stop(&reached));
+    }
+
+    #[test]
+    fn test_progress_of_combined_criteria() {
+        let mut state = state(50, 2.0);
+        state.generations = 8;
+        let criteria = || -> Vec<Box<dyn StoppingCriterion + Send + Sync>> {
+            vec![
+                Box::new(MaxSimulations(200)),
+                Box::new(MaxGenerations(10)),
+                Box::new(TargetFitness(1.0)),
+            ]
+        };
+
+        assert_eq!(TargetFitness(1.0).progress(&state), None);
+        assert_eq!(Any(criteria()).progress(&state), Some(0.8));
+        assert_eq!(All(criteria()).progress(&state), Some(0.25));
+    }
+}
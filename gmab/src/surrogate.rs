@@ -0,0 +1,8 @@
+// cheap model of the optimization function used to pre-screen offspring
+pub trait Surrogate {
+    // predicted fitness of an action vector
+    fn predict(&self, action_vector: &[i32]) -> f64;
+
+    // called with every real evaluation
+    fn update(&mut self, action_vector: &[i32], fitness: f64);
+}
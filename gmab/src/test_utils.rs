@@ -0,0 +1,97 @@
+//! Helpers for tests of code built on the crate, enabled with the `test-utils` feature.
+
+use alloc::vec::Vec;
+
+use crate::arm::Arm;
+
+// whether two fitnesses differ by at most `eps`, equal infinities count as near
+pub fn fitness_near(actual: f64, expected: f64, eps: f64) -> bool {
+    actual == expected || (actual - expected).abs() <= eps
+}
+
+// panics unless the mean reward of the arm lies within `eps` of `expected`
+#[track_caller]
+pub fn assert_arm_fitness_near(arm: &Arm, expected: f64, eps: f64) {
+    let actual = arm.get_mean_reward();
+    assert!(
+        fitness_near(actual, expected, eps),
+        "fitness of {:?} is {}, expected {} within {}",
+        arm.get_action_vector(),
+        actual,
+        expected,
+        eps
+    );
+}
+
+// arm that has been pulled once per reward
+pub fn arm_with_rewards(action_vector: &[i32], rewards: &[f64]) -> Arm {
+    let mut arm = Arm::new(action_vector);
+    for &reward in rewards {
+        arm.record_reward(reward);
+    }
+    arm
+}
+
+// unpulled arms in the given order
+pub fn population(action_vectors: &[&[i32]]) -> Vec<Arm> {
+    action_vectors
+        .iter()
+        .map(|action_vector| Arm::new(action_vector))
+        .collect()
+}
+
+// every point of the search space in lexicographic order, each evaluated once by `function`
+pub fn evaluated_grid(bounds: &[(i32, i32)], function: impl Fn(&[i32]) -> f64) -> Vec<Arm> {
+    let mut points: Vec<Vec<i32>> = alloc::vec![Vec::new()];
+    for &(lower, upper) in bounds {
+        points = points
+            .into_iter()
+            .flat_map(|point| {
+                (lower..=upper).map(move |value| {
+                    let mut point = point.clone();
+                    point.push(value);
+                    point
+                })
+            })
+            .collect();
+    }
+    points
+        .iter()
+        .map(|point| arm_with_rewards(point, &[function(point)]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assert_arm_fitness_near_within_tolerance() {
+        let arm = arm_with_rewards(&[1, 2], &[0.1, 0.2]);
+        assert_arm_fitness_near(&arm, 0.15, 1e-12);
+        assert_arm_fitness_near(&arm, 0.16, 0.02);
+        assert!(fitness_near(f64::INFINITY, f64::INFINITY, 0.0));
+        assert!(!fitness_near(f64::NAN, f64::NAN, 1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 0.2 within 0.01")]
+    fn test_assert_arm_fitness_near_outside_tolerance() {
+        let arm = arm_with_rewards(&[1, 2], &[0.1, 0.2]);
+        assert_arm_fitness_near(&arm, 0.2, 0.01);
+    }
+
+    #[test]
+    fn test_fixtures() {
+        let arms = population(&[&[0, 1], &[2, 3]]);
+        assert_eq!(arms, alloc::vec![Arm::new(&[0, 1]), Arm::new(&[2, 3])]);
+        assert!(arms.iter().all(|arm| arm.get_num_pulls() == 0));
+
+        let grid = evaluated_grid(&[(0, 1), (5, 7)], |x| (x[0] + x[1]) as f64);
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[0].get_action_vector(), &[0, 5]);
+        assert_eq!(grid[5].get_action_vector(), &[1, 7]);
+        assert_arm_fitness_near(&grid[4], 7.0, 0.0);
+        assert_eq!(grid[4].get_num_pulls(), 1);
+    }
+}
@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::panic::RefUnwindSafe;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::arm::OptimizationFn;
+use crate::config::GmabConfig;
+use crate::error::GaError;
+use crate::gmab::Gmab;
+
+// everything needed to reproduce a run: the configuration including its seed and the
+// rewards returned by the optimization function, in the order they were drawn
+#[derive(Debug, Clone)]
+pub struct RunTrace {
+    pub config: GmabConfig,
+    pub rewards: Vec<f64>,
+}
+
+// FNV-1a over the Debug output of the configuration, written along with the trace so that
+// replaying it with another configuration is detected
+fn fingerprint(config: &GmabConfig) -> u64 {
+    struct Fnv(u64);
+
+    impl fmt::Write for Fnv {
+        fn write_str(&mut self, text: &str) -> fmt::Result {
+            for &byte in text.as_bytes() {
+                self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01B3);
+            }
+            Ok(())
+        }
+    }
+
+    let mut hasher = Fnv(0xCBF2_9CE4_8422_2325);
+    fmt::write(&mut hasher, format_args!("{:?}", config)).expect("hashing cannot fail");
+    hasher.0
+}
+
+impl RunTrace {
+    // reads the text written by Display; the configuration itself is not part of the text,
+    // only its fingerprint, `config` has to be the one the trace was recorded with
+    pub fn from_text(text: &str, config: GmabConfig) -> Result<RunTrace, GaError> {
+        let mut lines = text.lines();
+        let seed = lines
+            .next()
+            .and_then(|line| line.strip_prefix("seed "))
+            .and_then(|seed| seed.trim().parse::<u64>().ok())
+            .ok_or(GaError::InvalidTrace)?;
+        let recorded_fingerprint = lines
+            .next()
+            .and_then(|line| line.strip_prefix("config "))
+            .and_then(|fingerprint| u64::from_str_radix(fingerprint.trim(), 16).ok())
+            .ok_or(GaError::InvalidTrace)?;
+        let rewards = lines
+            .next()
+            .and_then(|line| line.strip_prefix("rewards"))
+            .ok_or(GaError::InvalidTrace)?
+            .split_whitespace()
+            .map(|reward| reward.parse::<f64>().map_err(|_| GaError::InvalidTrace))
+            .collect::<Result<Vec<f64>, GaError>>()?;
+
+        let config = config.with_seed(seed);
+        if fingerprint(&config) != recorded_fingerprint {
+            return Err(GaError::TraceConfigMismatch);
+        }
+
+        Ok(RunTrace { config, rewards })
+    }
+}
+
+impl fmt::Display for RunTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "seed {}", self.config.seed.unwrap_or_default())?;
+        writeln!(f, "config {:016x}", fingerprint(&self.config))?;
+        write!(f, "rewards")?;
+        // Debug formatting of floats round-trips exactly
+        for reward in &self.rewards {
+            write!(f, " {:?}", reward)?;
+        }
+        writeln!(f)
+    }
+}
+
+// answers with the recorded rewards and falls back to the wrapped function once they run out
+pub struct Replay<F: OptimizationFn> {
+    opti_function: F,
+    rewards: Vec<f64>,
+    next: AtomicUsize,
+}
+
+impl<F: OptimizationFn> OptimizationFn for Replay<F> {
+    fn evaluate(&self, action_vector: &[i32]) -> f64 {
+        self.evaluate_replication(action_vector, 0)
+    }
+
+    fn evaluate_replication(&self, action_vector: &[i32], replication: u64) -> f64 {
+        let index = self.next.fetch_add(1, Ordering::Relaxed);
+        match self.rewards.get(index) {
+            Some(&reward) => reward,
+            None => self
+                .opti_function
+                .evaluate_replication(action_vector, replication),
+        }
+    }
+
+    fn set_time(&self, time: usize) {
+        self.opti_function.set_time(time);
+    }
+}
+
+// rebuilds the run of `trace`; stepping the returned optimizer repeats the recorded
+// generations exactly
+pub fn replay<F: OptimizationFn + RefUnwindSafe>(
+    trace: &RunTrace,
+    opti_function: F,
+) -> Gmab<Replay<F>> {
+    let replay_function = Replay {
+        opti_function,
+        rewards: trace.rewards.clone(),
+        next: AtomicUsize::new(0),
+    };
+    Gmab::with_config(replay_function, trace.config.clone())
+}
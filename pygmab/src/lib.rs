@@ -1,3 +1,5 @@
+use std::panic::RefUnwindSafe;
+
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 
@@ -14,6 +16,10 @@ impl PythonOptimizationFn {
     }
 }
 
+// the Python function is only called while holding the GIL, a panic leaves no state behind
+// that could be observed broken
+impl RefUnwindSafe for PythonOptimizationFn {}
+
 impl OptimizationFn for PythonOptimizationFn {
     fn evaluate(&self, action_vector: &[i32]) -> f64 {
         Python::with_gil(|py| {